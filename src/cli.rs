@@ -0,0 +1,277 @@
+use std::path::PathBuf;
+
+use clap::{
+    error::ErrorKind, parser::ValueSource, Args, CommandFactory, FromArgMatches, Parser, Subcommand,
+};
+use clap_complete::Shell;
+use commitz::commit::lint::ReportFormat;
+
+pub const EXIT_FAILURE: i32 = 1;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "commitz",
+    about = "Write Conventional Commits from an interactive prompt",
+    version,
+    disable_version_flag = true
+)]
+pub struct Cli {
+    /// Print version
+    #[arg(short = 'v', long = "version", action = clap::ArgAction::Version)]
+    pub version: Option<bool>,
+
+    /// Override a config value, e.g. -c rules.header_max_length=72
+    #[arg(short = 'c', value_name = "KEY=VALUE", global = true)]
+    pub overrides: Vec<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub commit: CommitArgs,
+}
+
+impl Cli {
+    /// Parses the command line. Like `git -c`, the global `-c` may come before a
+    /// subcommand, but the options of the default commit command may not.
+    pub fn parse_args() -> Cli {
+        let mut command = Cli::command();
+        command.build();
+        let matches = command.clone().get_matches();
+        let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+        if let Some(name) = matches.subcommand_name() {
+            let commit_arg = command.get_arguments().find(|arg| {
+                !arg.is_global_set()
+                    && matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            });
+            if let Some(arg) = commit_arg {
+                let message = format!(
+                    "the argument '{}' cannot be used with the '{}' subcommand",
+                    arg, name
+                );
+                command
+                    .clone()
+                    .error(ErrorKind::ArgumentConflict, message)
+                    .exit();
+            }
+        }
+
+        cli
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Compose a commit interactively (default)
+    Commit(CommitArgs),
+    /// Check commit messages against the configured rules
+    Lint(LintArgs),
+    /// Write a starter config file to the repository root
+    Init(InitArgs),
+    /// Run as a prepare-commit-msg hook, or manage git hooks
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Hook {
+        #[command(subcommand)]
+        command: Option<HookCommand>,
+        #[command(flatten)]
+        args: HookArgs,
+    },
+    /// Print a markdown changelog grouped by commit type
+    Changelog(ChangelogArgs),
+    /// Inspect the resolved configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Print shell completions
+    Completions { shell: Shell },
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct CommitArgs {
+    /// Commit type, e.g. feat
+    #[arg(long = "type", value_name = "TYPE")]
+    pub commit_type: Option<String>,
+    /// Commit scope, use "" for none
+    #[arg(long)]
+    pub scope: Option<String>,
+    /// Short description of the change
+    #[arg(long, allow_hyphen_values = true)]
+    pub subject: Option<String>,
+    /// Longer description of the change
+    #[arg(long, allow_hyphen_values = true)]
+    pub body: Option<String>,
+    /// Mark the commit as breaking
+    #[arg(long, value_name = "NOTE", allow_hyphen_values = true)]
+    pub breaking: Option<String>,
+    /// Issue footer, e.g. "Closes #12"
+    #[arg(long, allow_hyphen_values = true)]
+    pub issues: Option<String>,
+    /// Skip the confirmation prompt
+    #[arg(short, long)]
+    pub yes: bool,
+    /// Edit the HEAD commit message and run `git commit --amend`
+    #[arg(long)]
+    pub amend: bool,
+    /// Commit again with the message of the last failed commit
+    #[arg(long, conflicts_with_all = ["commit_type", "scope", "subject", "body", "breaking", "issues", "dry_run", "output"])]
+    pub retry: bool,
+    /// Print the message instead of running `git commit`
+    #[arg(long, visible_alias = "print")]
+    pub dry_run: bool,
+    /// Write the message to a file instead of stdout (implies --dry-run)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+    /// Skip the pre-commit and commit-msg hooks
+    #[arg(long, help_heading = "Git commit options")]
+    pub no_verify: bool,
+    /// GPG-sign the commit
+    #[arg(
+        short = 'S',
+        long,
+        value_name = "KEYID",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "",
+        help_heading = "Git commit options"
+    )]
+    pub gpg_sign: Option<String>,
+    /// Add a Signed-off-by trailer
+    #[arg(short, long, help_heading = "Git commit options")]
+    pub signoff: bool,
+    /// Override the commit author
+    #[arg(long, value_name = "AUTHOR", help_heading = "Git commit options")]
+    pub author: Option<String>,
+    /// Override the author date
+    #[arg(long, value_name = "DATE", help_heading = "Git commit options")]
+    pub date: Option<String>,
+    /// Allow a commit without staged changes
+    #[arg(long, help_heading = "Git commit options")]
+    pub allow_empty: bool,
+    /// Any other options, passed to git commit as they are
+    #[arg(last = true, value_name = "GIT_ARGS")]
+    pub git_args: Vec<String>,
+}
+
+impl CommitArgs {
+    /// The options forwarded to `git commit`, in the order git documents them.
+    pub fn git_options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+        if self.amend {
+            options.push("--amend".to_string());
+        }
+        if self.no_verify {
+            options.push("--no-verify".to_string());
+        }
+        match self.gpg_sign.as_deref() {
+            Some("") => options.push("--gpg-sign".to_string()),
+            Some(key) => options.push(format!("--gpg-sign={}", key)),
+            None => {}
+        }
+        if self.signoff {
+            options.push("--signoff".to_string());
+        }
+        if let Some(author) = &self.author {
+            options.push(format!("--author={}", author));
+        }
+        if let Some(date) = &self.date {
+            options.push(format!("--date={}", date));
+        }
+        if self.allow_empty {
+            options.push("--allow-empty".to_string());
+        }
+        options.extend(self.git_args.iter().cloned());
+        options
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct LintArgs {
+    /// Lint a commit message file, as a commit-msg hook
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["from", "to"])]
+    pub edit: Option<String>,
+    /// Lint commits after this revision
+    #[arg(long, value_name = "REV")]
+    pub from: Option<String>,
+    /// Lint commits up to this revision
+    #[arg(long, value_name = "REV", default_value = "HEAD")]
+    pub to: String,
+    /// Report format
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// Write commitz.toml instead of .commitz.json
+    #[arg(long)]
+    pub toml: bool,
+    /// Overwrite an existing config file
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct HookArgs {
+    /// Commit message file passed in by git
+    #[arg(value_name = "MSG_FILE", required = true)]
+    pub file: Option<PathBuf>,
+    /// Source of the message: message, template, merge, squash or commit
+    pub source: Option<String>,
+    /// Commit being amended, when the source is commit
+    pub sha: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HookCommand {
+    /// Install the git hooks that run commitz (both unless one is chosen)
+    Install(HookInstallArgs),
+    /// Remove the hooks installed by commitz
+    Uninstall(HookSelection),
+}
+
+#[derive(Debug, Args)]
+pub struct HookInstallArgs {
+    #[command(flatten)]
+    pub hooks: HookSelection,
+    /// Overwrite hooks that were not installed by commitz
+    #[arg(short, long, conflicts_with = "chain")]
+    pub force: bool,
+    /// Keep existing hooks and run them before commitz
+    #[arg(long)]
+    pub chain: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct HookSelection {
+    /// The commit-msg hook that lints every message
+    #[arg(long)]
+    pub commit_msg: bool,
+    /// The prepare-commit-msg hook that prompts on `git commit`
+    #[arg(long)]
+    pub prepare_commit_msg: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ChangelogArgs {
+    /// Start after this revision (default: latest tag)
+    #[arg(long, value_name = "REV")]
+    pub from: Option<String>,
+    /// End at this revision
+    #[arg(long, value_name = "REV", default_value = "HEAD")]
+    pub to: String,
+    /// Heading of the changelog section
+    #[arg(long, default_value = "Unreleased")]
+    pub title: String,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the merged configuration
+    Show {
+        /// Show which layer each value came from
+        #[arg(long)]
+        resolved: bool,
+    },
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+
+use crate::commit::{
+    config::Config,
+    message::{ConventionalCommit, BREAKING_CHANGE_TOKENS},
+    types::RenderCommit,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    pub commit_type: Option<String>,
+    pub scope: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub breaking: Option<bool>,
+    pub breaking_note: Option<String>,
+    pub issues: Option<String>,
+    /// Footers carried over verbatim that no prompt asks for, e.g. `Signed-off-by`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub footers: Vec<String>,
+}
+
+impl Answers {
+    pub fn fill_defaults(&mut self) {
+        self.scope.get_or_insert_with(String::new);
+        self.body.get_or_insert_with(String::new);
+        self.breaking_note.get_or_insert_with(String::new);
+        self.issues.get_or_insert_with(String::new);
+        let has_note = self.breaking_note.as_ref().is_some_and(|n| !n.is_empty());
+        self.breaking.get_or_insert(has_note);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.commit_type.is_some()
+            && self.scope.is_some()
+            && self.subject.is_some()
+            && self.body.is_some()
+            && self.breaking.is_some()
+            && self.breaking_note.is_some()
+            && self.issues.is_some()
+    }
+
+    pub fn header_prefix(&self, config: &Config) -> String {
+        let scope = self
+            .scope
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .map(|s| format!("({})", s))
+            .unwrap_or_default();
+        let marker = self.breaking_marker(config);
+
+        format!(
+            "{}{}{}: ",
+            self.commit_type.as_deref().unwrap_or_default(),
+            scope,
+            if marker { "!" } else { "" }
+        )
+    }
+
+    /// Whether the header gets a `!`. Without a note there is no `BREAKING CHANGE:`
+    /// footer to carry the flag, so the marker is used whatever the configured style.
+    pub fn breaking_marker(&self, config: &Config) -> bool {
+        let has_note = self
+            .breaking_note
+            .as_deref()
+            .is_some_and(|note| !note.trim().is_empty());
+        self.breaking == Some(true) && (config.breaking_change_style.uses_header() || !has_note)
+    }
+
+    pub fn validate(&self, config: &Config) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        match self.commit_type.as_deref() {
+            None => errors.push("commit type is required".to_string()),
+            Some(key) if !config.types.iter().any(|t| t.key == key) => {
+                let allowed: Vec<&str> = config.types.iter().map(|t| t.key.as_str()).collect();
+                errors.push(format!(
+                    "type `{}` is not one of: {}",
+                    key,
+                    allowed.join(", ")
+                ));
+            }
+            _ => {}
+        }
+
+        match self.scope.as_deref().unwrap_or_default() {
+            "" if config.scope_required => errors.push("scope is required".to_string()),
+            "" => {}
+            scope if !config.scope_allowed(scope) => {
+                errors.push(format!("scope `{}` is not in the configured scopes", scope))
+            }
+            _ => {}
+        }
+
+        let subject = self.subject.as_deref().unwrap_or_default();
+        errors.extend(config.rules.check_subject(subject));
+        let header = format!("{}{}", self.header_prefix(config), subject.trim());
+        errors.extend(config.rules.check_header_length(&header));
+
+        errors
+    }
+
+    pub fn render(&self, config: &Config) -> RenderCommit {
+        let scope = self.scope.as_deref().unwrap_or_default().trim();
+        let issues = self.issues.as_deref().unwrap_or_default().trim();
+        let (issue_prefix, issue_refs) = issues
+            .split_once(char::is_whitespace)
+            .unwrap_or((issues, ""));
+
+        RenderCommit::new(
+            self.commit_type.clone().unwrap_or_default(),
+            if scope.is_empty() {
+                String::new()
+            } else {
+                format!("({})", scope)
+            },
+            self.subject
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            config
+                .rules
+                .wrap_body(self.body.as_deref().unwrap_or_default()),
+            config.rules.wrap_footer(
+                BREAKING_CHANGE_TOKENS[0],
+                self.breaking_note.as_deref().unwrap_or_default(),
+            ),
+            issue_prefix.to_string(),
+            issue_refs.trim().to_string(),
+        )
+        .with_breaking_marker(self.breaking_marker(config))
+        .with_extra_footers(self.footers.clone())
+    }
+
+    /// Splits an existing message into answers, so it can be edited prompt by prompt.
+    /// A header that does not follow Conventional Commits becomes the subject.
+    pub fn from_message(message: &str) -> Answers {
+        let Ok(commit) = ConventionalCommit::parse(message) else {
+            let message = message.trim();
+            let (subject, body) = message.split_once('\n').unwrap_or((message, ""));
+            return Answers {
+                subject: Some(subject.trim().to_string()),
+                body: Some(body.trim().to_string()),
+                ..Answers::default()
+            };
+        };
+
+        let breaking_note = commit.breaking_notes().first().copied().unwrap_or_default();
+        let issues = commit.footers.iter().position(|f| f.is_issue_reference());
+        let footers = commit
+            .footers
+            .iter()
+            .enumerate()
+            .filter(|&(i, f)| !f.is_breaking_change() && Some(i) != issues)
+            .map(|(_, f)| f.to_string())
+            .collect();
+
+        Answers {
+            commit_type: Some(commit.commit_type.clone()),
+            scope: Some(commit.scope.clone().unwrap_or_default()),
+            subject: Some(commit.description.clone()),
+            body: Some(commit.body.clone().unwrap_or_default()),
+            breaking: Some(commit.is_breaking()),
+            breaking_note: Some(breaking_note.to_string()),
+            issues: Some(
+                issues
+                    .map(|i| commit.footers[i].to_string())
+                    .unwrap_or_default(),
+            ),
+            footers,
+        }
+    }
+
+    /// Fills every answer that is still `None` from `other`.
+    pub fn or(mut self, other: Answers) -> Answers {
+        self.commit_type = self.commit_type.or(other.commit_type);
+        self.scope = self.scope.or(other.scope);
+        self.subject = self.subject.or(other.subject);
+        self.body = self.body.or(other.body);
+        self.breaking = self.breaking.or(other.breaking);
+        self.breaking_note = self.breaking_note.or(other.breaking_note);
+        self.issues = self.issues.or(other.issues);
+        if self.footers.is_empty() {
+            self.footers = other.footers;
+        }
+        self
+    }
+}
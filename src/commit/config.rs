@@ -0,0 +1,382 @@
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commit::{
+    rules::Rules,
+    types::{CommitType, Scope, ScopeRule},
+};
+
+pub const CONFIG_FILE_NAMES: [&str; 2] = [".commitz.json", "commitz.toml"];
+pub const GLOBAL_CONFIG_FILE_NAMES: [&str; 2] = ["config.json", "config.toml"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigSource {
+    Default,
+    Global(PathBuf),
+    Repository(PathBuf),
+    Cli,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub config: Config,
+    pub values: Value,
+    pub sources: BTreeMap<String, ConfigSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub types: Vec<CommitType>,
+    pub scopes: Vec<Scope>,
+    pub allow_custom_scopes: bool,
+    pub scope_required: bool,
+    pub infer_scopes: bool,
+    pub workspace_scopes: bool,
+    pub scope_rules: Vec<ScopeRule>,
+    pub breaking_change_style: BreakingChangeStyle,
+    pub rules: Rules,
+    pub messages: Messages,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BreakingChangeStyle {
+    Header,
+    Footer,
+    Both,
+}
+
+impl BreakingChangeStyle {
+    pub fn uses_header(self) -> bool {
+        matches!(
+            self,
+            BreakingChangeStyle::Header | BreakingChangeStyle::Both
+        )
+    }
+
+    pub fn uses_footer(self) -> bool {
+        matches!(
+            self,
+            BreakingChangeStyle::Footer | BreakingChangeStyle::Both
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Messages {
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    pub scope: String,
+    pub custom_scope: String,
+    pub subject: String,
+    pub body: String,
+    pub breaking_confirm: String,
+    pub breaking: String,
+    pub footer_prefix: String,
+    pub footer: String,
+    pub confirm: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            types: CommitType::load(),
+            scopes: Vec::new(),
+            allow_custom_scopes: true,
+            scope_required: false,
+            infer_scopes: true,
+            workspace_scopes: true,
+            scope_rules: Vec::new(),
+            breaking_change_style: BreakingChangeStyle::Footer,
+            rules: Rules::default(),
+            messages: Messages::default(),
+        }
+    }
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Messages {
+            commit_type: "Select the type of change that you're committing: ".to_string(),
+            scope: "Denote the SCOPE of this change (optional):".to_string(),
+            custom_scope: "Denote the SCOPE of this change: ".to_string(),
+            subject: "Write a SHORT, IMPERATIVE tense description of the change:".to_string(),
+            body: "Provide a LONGER description of the change (optional):".to_string(),
+            breaking_confirm: "Is this a BREAKING CHANGE?".to_string(),
+            breaking: "Describe the BREAKING CHANGES:".to_string(),
+            footer_prefix: "Select the ISSUES type of change (optional), Input ISSUES prefix\n"
+                .to_string(),
+            footer: "List any ISSUES AFFECTED by this change. E.g.: #31, #34: ".to_string(),
+            confirm: "Are you sure you want to proceed with the commit above?".to_string(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load(overrides: &[String]) -> io::Result<Config> {
+        Ok(Config::resolve(overrides)?.config)
+    }
+
+    pub fn resolve(overrides: &[String]) -> io::Result<ResolvedConfig> {
+        let mut values = to_value(&Config::default())?;
+        let mut sources = BTreeMap::new();
+        record_sources(&values, &ConfigSource::Default, &mut sources);
+
+        let mut layers = Vec::new();
+        if let Some(path) = find_global_config() {
+            layers.push((read_layer(&path)?, ConfigSource::Global(path)));
+        }
+        if let Some(path) = find_project_config()? {
+            layers.push((read_layer(&path)?, ConfigSource::Repository(path)));
+        }
+        if !overrides.is_empty() {
+            layers.push((parse_overrides(overrides)?, ConfigSource::Cli));
+        }
+
+        for (layer, source) in layers {
+            record_sources(&layer, &source, &mut sources);
+            merge(&mut values, layer);
+        }
+
+        let config =
+            Config::deserialize(values.clone()).map_err(|e| invalid_data(e.to_string()))?;
+        if config.types.is_empty() {
+            return Err(invalid_data(
+                "`types` must list at least one commit type".to_string(),
+            ));
+        }
+        if config.scope_required && !config.allow_custom_scopes && config.scopes.is_empty() {
+            return Err(invalid_data(
+                "`scope_required` needs `scopes` or `allow_custom_scopes`".to_string(),
+            ));
+        }
+        if let Some(scope) = config
+            .scopes
+            .iter()
+            .find(|s| s.name == Scope::EMPTY || s.name == Scope::CUSTOM)
+        {
+            return Err(invalid_data(format!(
+                "scope `{}` is reserved, pick another name",
+                scope.name
+            )));
+        }
+
+        Ok(ResolvedConfig {
+            config,
+            values,
+            sources,
+        })
+    }
+}
+
+impl Config {
+    pub fn scope_allowed(&self, scope: &str) -> bool {
+        self.allow_custom_scopes || self.scopes.iter().any(|s| s.name == scope)
+    }
+
+    pub fn scope_options(&self, suggested: &[String]) -> Vec<Scope> {
+        let mut options: Vec<Scope> = suggested
+            .iter()
+            .map(|name| match self.scopes.iter().find(|s| &s.name == name) {
+                Some(scope) => scope.clone(),
+                None => Scope::new(name, "suggested from staged files"),
+            })
+            .collect();
+        options.extend(
+            self.scopes
+                .iter()
+                .filter(|s| !suggested.contains(&s.name))
+                .cloned(),
+        );
+        if !self.scope_required {
+            options.push(Scope::new(Scope::EMPTY, ""));
+        }
+        if self.allow_custom_scopes {
+            options.push(Scope::new(Scope::CUSTOM, ""));
+        }
+        options
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Global(path) => write!(f, "global ({})", path.display()),
+            ConfigSource::Repository(path) => write!(f, "repository ({})", path.display()),
+            ConfigSource::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+impl fmt::Display for ResolvedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut leaves = Vec::new();
+        collect_leaves(&self.values, "", &mut leaves);
+
+        for (key, value) in leaves {
+            let source = self
+                .sources
+                .get(&key)
+                .cloned()
+                .unwrap_or(ConfigSource::Default);
+            writeln!(f, "{} = {}  # {}", key, value, source)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn global_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("commitz"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = env::var_os("APPDATA") {
+            return Some(PathBuf::from(dir).join("commitz"));
+        }
+    }
+
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("commitz"))
+}
+
+pub fn find_global_config() -> Option<PathBuf> {
+    let dir = global_config_dir()?;
+    GLOBAL_CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+pub fn find_project_config() -> io::Result<Option<PathBuf>> {
+    let root = repo_root();
+    let mut dir = fs::canonicalize(std::env::current_dir()?)?;
+
+    loop {
+        for name in CONFIG_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Ok(Some(candidate));
+            }
+        }
+
+        if root.as_deref() == Some(dir.as_path()) || !dir.pop() {
+            return Ok(None);
+        }
+    }
+}
+
+pub fn repo_root() -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    fs::canonicalize(root).ok()
+}
+
+fn read_layer(path: &Path) -> io::Result<Value> {
+    let content = fs::read_to_string(path)?;
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+
+    let value = if is_toml {
+        let table: toml::Table = toml::from_str(&content)
+            .map_err(|e| invalid_data(format!("{}: {}", path.display(), e)))?;
+        to_value(&table)?
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|e| invalid_data(format!("{}: {}", path.display(), e)))?
+    };
+
+    Config::deserialize(&value).map_err(|e| invalid_data(format!("{}: {}", path.display(), e)))?;
+    Ok(value)
+}
+
+fn parse_overrides(overrides: &[String]) -> io::Result<Value> {
+    let mut layer = Value::Object(Default::default());
+
+    for item in overrides {
+        let Some((key, raw)) = item.split_once('=') else {
+            return Err(invalid_data(format!(
+                "invalid override `{}`, expected key=value",
+                item
+            )));
+        };
+
+        let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+        let nested = key.trim().rsplit('.').fold(value, |acc, part| {
+            Value::Object([(part.to_string(), acc)].into_iter().collect())
+        });
+        merge(&mut layer, nested);
+    }
+
+    Config::deserialize(&layer).map_err(|e| invalid_data(format!("command line: {}", e)))?;
+    Ok(layer)
+}
+
+fn record_sources(
+    value: &Value,
+    source: &ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    let mut leaves = Vec::new();
+    collect_leaves(value, "", &mut leaves);
+    for (key, _) in leaves {
+        sources.insert(key, source.clone());
+    }
+}
+
+fn collect_leaves(value: &Value, prefix: &str, leaves: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                collect_leaves(value, &path, leaves);
+            }
+        }
+        _ => leaves.push((prefix.to_string(), value.clone())),
+    }
+}
+
+fn merge(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+fn to_value(value: &impl Serialize) -> io::Result<Value> {
+    serde_json::to_value(value).map_err(|e| invalid_data(e.to_string()))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
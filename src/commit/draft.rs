@@ -0,0 +1,80 @@
+use std::{fs, io, path::PathBuf};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::commit::{answers::Answers, git::git_path, types::RenderCommit};
+
+/// The answers collected so far, checkpointed after every prompt.
+pub const ANSWERS_DRAFT_FILE: &str = "COMMITZ_DRAFT.json";
+/// The last composed commit, kept in the git directory until `git commit` succeeds.
+pub const RENDER_DRAFT_FILE: &str = "COMMITZ_RENDER.json";
+
+pub fn save_answers(answers: &Answers) -> io::Result<()> {
+    save(ANSWERS_DRAFT_FILE, answers)
+}
+
+pub fn load_answers() -> io::Result<Option<Answers>> {
+    Ok(load::<Answers>(ANSWERS_DRAFT_FILE)?.filter(|answers| *answers != Answers::default()))
+}
+
+pub fn discard_answers() -> io::Result<()> {
+    discard(ANSWERS_DRAFT_FILE)
+}
+
+/// A composed commit together with the options it was committed with, so `--retry`
+/// commits it the same way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDraft {
+    #[serde(flatten)]
+    pub render: RenderCommit,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_options: Vec<String>,
+}
+
+pub fn save_render(render: &RenderCommit, git_options: &[String]) -> io::Result<()> {
+    let draft = CommitDraft {
+        render: render.clone(),
+        git_options: git_options.to_vec(),
+    };
+    save(RENDER_DRAFT_FILE, &draft)
+}
+
+pub fn load_render() -> io::Result<Option<CommitDraft>> {
+    load(RENDER_DRAFT_FILE)
+}
+
+pub fn discard_render() -> io::Result<()> {
+    discard(RENDER_DRAFT_FILE)
+}
+
+fn save(name: &str, value: &impl Serialize) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    fs::write(draft_path(name)?, json)
+}
+
+fn load<T: DeserializeOwned>(name: &str) -> io::Result<Option<T>> {
+    let path = draft_path(name)?;
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let json = fs::read_to_string(&path)?;
+    serde_json::from_str(&json).map(Some).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })
+}
+
+fn discard(name: &str) -> io::Result<()> {
+    let path = draft_path(name)?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn draft_path(name: &str) -> io::Result<PathBuf> {
+    git_path(name)
+}
@@ -0,0 +1,63 @@
+use std::{
+    fs, io,
+    process::{Command, Stdio},
+};
+
+use crate::commit::{git::git_path, message::message_file_lines};
+
+/// The editor git would use, resolved by `git var GIT_EDITOR` from `$GIT_EDITOR`,
+/// `core.editor`, `$VISUAL` and `$EDITOR`, in that order.
+pub fn editor_command() -> io::Result<String> {
+    let output = Command::new("git")
+        .args(["var", "GIT_EDITOR"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    let editor = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || editor.is_empty() {
+        return Err(io::Error::other(
+            "no editor configured, set $EDITOR or core.editor",
+        ));
+    }
+
+    Ok(editor)
+}
+
+/// Opens `content` in the editor from a file in the git directory and returns what was
+/// saved, with comment lines stripped the way `git commit` does.
+pub fn edit_text(file_name: &str, content: &str) -> io::Result<String> {
+    let path = git_path(file_name)?;
+    fs::write(&path, content)?;
+
+    let editor = editor_command()?;
+    let status = editor_process(&editor, &path.to_string_lossy()).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("editor `{}` failed", editor)));
+    }
+
+    let edited = fs::read_to_string(&path)?;
+    fs::remove_file(&path)?;
+    Ok(strip_comments(&edited))
+}
+
+/// Drops `#` comment lines and everything below the scissors line, then trims
+/// surrounding blank lines and trailing whitespace.
+pub fn strip_comments(text: &str) -> String {
+    let lines: Vec<&str> = message_file_lines(text)
+        .map(|(_, line)| line.trim_end())
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+#[cfg(not(windows))]
+fn editor_process(editor: &str, path: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", &format!("{} \"$@\"", editor), editor, path]);
+    command
+}
+
+#[cfg(windows)]
+fn editor_process(editor: &str, path: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", &format!("{} \"{}\"", editor, path)]);
+    command
+}
@@ -0,0 +1,66 @@
+use std::{
+    io,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// The message file commitz hands to `git commit -F`, relative to the git directory.
+pub const MESSAGE_FILE: &str = "COMMITZ_EDITMSG";
+
+pub struct LoggedCommit {
+    pub sha: String,
+    pub message: String,
+}
+
+pub fn log_messages(from: Option<&str>, to: &str) -> io::Result<Vec<LoggedCommit>> {
+    let range = match from {
+        Some(from) => format!("{}..{}", from, to),
+        None => to.to_string(),
+    };
+
+    let output = Command::new("git")
+        .args(["log", "--reverse", "--format=%H%x00%B%x1e", &range])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git log {} failed", range)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\x1e')
+        .filter_map(|entry| entry.trim_start_matches('\n').split_once('\0'))
+        .map(|(sha, message)| LoggedCommit {
+            sha: sha.to_string(),
+            message: message.to_string(),
+        })
+        .collect())
+}
+
+pub fn git_path(name: &str) -> io::Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--path-format=absolute", "--git-path", name])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git rev-parse --git-path {} failed",
+            name
+        )));
+    }
+
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+    ))
+}
+
+pub fn head_message() -> io::Result<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%B", "HEAD"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("there is no commit to amend"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
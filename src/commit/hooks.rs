@@ -0,0 +1,228 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::commit::git::git_path;
+
+pub const HOOK_MARKER: &str = "# Installed by commitz";
+pub const CHAINED_SUFFIX: &str = ".pre-commitz";
+
+/// Message sources for which git already has a message, so the prompt is skipped.
+const SKIPPED_SOURCES: [&str; 4] = ["message", "merge", "squash", "commit"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookKind {
+    CommitMsg,
+    PrepareCommitMsg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExistingHook {
+    Refuse,
+    Overwrite,
+    Chain,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookChange {
+    Installed(PathBuf),
+    Chained(PathBuf, PathBuf),
+    Removed(PathBuf),
+    Restored(PathBuf),
+}
+
+impl HookKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            HookKind::CommitMsg => "commit-msg",
+            HookKind::PrepareCommitMsg => "prepare-commit-msg",
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            HookKind::CommitMsg => "commitz lint --edit \"$1\"",
+            HookKind::PrepareCommitMsg => "commitz hook \"$@\"",
+        }
+    }
+
+    fn script(self, chained: bool) -> String {
+        let chain = if chained {
+            format!(
+                "if [ -x \"$0{0}\" ]; then\n    \"$0{0}\" \"$@\" || exit $?\nfi\n",
+                CHAINED_SUFFIX
+            )
+        } else {
+            String::new()
+        };
+
+        format!(
+            "#!/bin/sh\n{}\n{}exec {}\n",
+            HOOK_MARKER,
+            chain,
+            self.command()
+        )
+    }
+}
+
+impl fmt::Display for HookChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookChange::Installed(path) => write!(f, "Installed {}", path.display()),
+            HookChange::Chained(path, previous) => write!(
+                f,
+                "Installed {}, existing hook kept as {}",
+                path.display(),
+                previous.display()
+            ),
+            HookChange::Removed(path) => write!(f, "Removed {}", path.display()),
+            HookChange::Restored(path) => write!(f, "Restored {}", path.display()),
+        }
+    }
+}
+
+/// The directory git runs hooks from, honoring `core.hooksPath` and linked worktrees.
+pub fn hooks_dir() -> io::Result<PathBuf> {
+    git_path("hooks")
+}
+
+/// Installs every hook in `kinds`, or none of them if one would clobber a foreign hook.
+pub fn install_hooks(
+    dir: &Path,
+    kinds: &[HookKind],
+    existing: ExistingHook,
+) -> io::Result<Vec<HookChange>> {
+    for &kind in kinds {
+        check_existing(&dir.join(kind.name()), existing)?;
+    }
+
+    kinds
+        .iter()
+        .map(|&kind| install_hook(dir, kind, existing))
+        .collect()
+}
+
+fn check_existing(path: &Path, existing: ExistingHook) -> io::Result<()> {
+    if !path.exists() || is_commitz_hook(path) {
+        return Ok(());
+    }
+
+    let chained_path = chained_path(path);
+    match existing {
+        ExistingHook::Overwrite => Ok(()),
+        ExistingHook::Chain if !chained_path.exists() => Ok(()),
+        ExistingHook::Chain => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", chained_path.display()),
+        )),
+        ExistingHook::Refuse => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} was not installed by commitz, use --chain to keep it or --force to overwrite it",
+                path.display()
+            ),
+        )),
+    }
+}
+
+fn install_hook(dir: &Path, kind: HookKind, existing: ExistingHook) -> io::Result<HookChange> {
+    let path = dir.join(kind.name());
+    let chained_path = chained_path(&path);
+    check_existing(&path, existing)?;
+
+    let foreign = path.exists() && !is_commitz_hook(&path);
+    let chained = if foreign && existing == ExistingHook::Chain {
+        fs::rename(&path, &chained_path)?;
+        true
+    } else {
+        !foreign && chained_path.exists()
+    };
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, kind.script(chained))?;
+    make_executable(&path)?;
+
+    Ok(if chained {
+        HookChange::Chained(path, chained_path)
+    } else {
+        HookChange::Installed(path)
+    })
+}
+
+pub fn uninstall_hook(dir: &Path, kind: HookKind) -> io::Result<Option<HookChange>> {
+    let path = dir.join(kind.name());
+    if !path.exists() || !is_commitz_hook(&path) {
+        return Ok(None);
+    }
+
+    let chained_path = chained_path(&path);
+    if chained_path.exists() {
+        fs::rename(&chained_path, &path)?;
+        return Ok(Some(HookChange::Restored(path)));
+    }
+
+    fs::remove_file(&path)?;
+    Ok(Some(HookChange::Removed(path)))
+}
+
+fn is_commitz_hook(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.lines().any(|l| l == HOOK_MARKER))
+}
+
+fn chained_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(CHAINED_SUFFIX);
+    path.with_file_name(name)
+}
+
+pub fn should_prompt(source: Option<&str>) -> bool {
+    !source.is_some_and(|source| SKIPPED_SOURCES.contains(&source))
+}
+
+/// Git runs hooks without a terminal on stdin and with stdout redirected, so the
+/// prompt is attached to the controlling terminal instead. Returns `false` when
+/// there is no terminal, e.g. when committing from an IDE.
+#[cfg(unix)]
+pub fn attach_terminal() -> io::Result<bool> {
+    use std::os::fd::AsRawFd;
+
+    let Ok(tty) = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+    else {
+        return Ok(false);
+    };
+    for fd in [0, 1] {
+        // SAFETY: `tty` stays open until the end of this function, so its fd is valid for
+        // the call. dup2 only replaces stdin/stdout with a copy of it; `tty` itself is
+        // closed afterwards without touching fds 0 and 1, and no Rust handle owns them.
+        if unsafe { libc::dup2(tty.as_raw_fd(), fd) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+pub fn attach_terminal() -> io::Result<bool> {
+    use std::io::IsTerminal;
+
+    Ok(io::stdout().is_terminal() || io::stderr().is_terminal())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
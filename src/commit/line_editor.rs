@@ -0,0 +1,197 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor,
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A single line of input with a cursor. Edits move by grapheme and drawing accounts
+/// for wide characters, so the line can wrap past the terminal width.
+pub struct LineEditor {
+    buffer: String,
+    /// Byte offset of the cursor, always on a grapheme boundary.
+    cursor: usize,
+    /// Column the line starts at, after the prompt text.
+    start_column: u16,
+    /// Screen rows taken by the last render, and the row the cursor was left on.
+    rows: u16,
+    cursor_row: u16,
+    cursor_column: u16,
+}
+
+impl LineEditor {
+    /// Starts editing `initial` at the current cursor position, with the cursor at the end.
+    pub fn new(initial: &str) -> io::Result<Self> {
+        let (start_column, _) = cursor::position()?;
+        Ok(LineEditor {
+            buffer: initial.to_string(),
+            cursor: initial.len(),
+            start_column,
+            rows: 1,
+            cursor_row: 0,
+            cursor_column: start_column,
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Applies an editing key. Returns `false` for keys the editor does not handle.
+    pub fn handle_key(&mut self, event: KeyEvent) -> bool {
+        let control = event.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = event.modifiers.contains(KeyModifiers::ALT);
+
+        match event.code {
+            KeyCode::Char('w') if control => self.delete_to(self.word_start()),
+            KeyCode::Char('u') if control => self.delete_to(0),
+            KeyCode::Char('b') if alt => self.cursor = self.word_start(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_end(),
+            KeyCode::Left if control || alt => self.cursor = self.word_start(),
+            KeyCode::Right if control || alt => self.cursor = self.word_end(),
+            KeyCode::Char(c) if !control && !alt => self.insert(&c.to_string()),
+            KeyCode::Backspace => self.delete_to(self.previous_boundary()),
+            KeyCode::Delete => {
+                let next = self.next_boundary();
+                self.buffer.replace_range(self.cursor..next, "");
+            }
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.buffer.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Inserts `text` at the cursor, e.g. a paste. Line breaks become spaces.
+    pub fn insert(&mut self, text: &str) {
+        let text = text.replace("\r\n", " ").replace(['\r', '\n'], " ");
+        self.buffer.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    fn delete_to(&mut self, start: usize) {
+        self.buffer.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.buffer[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.buffer[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |g| self.cursor + g.len())
+    }
+
+    /// Start of the word before the cursor, skipping the whitespace in between.
+    fn word_start(&self) -> usize {
+        let before = self.buffer[..self.cursor].trim_end();
+        before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8())
+    }
+
+    /// End of the word after the cursor, skipping the whitespace in between.
+    fn word_end(&self) -> usize {
+        let after = &self.buffer[self.cursor..];
+        let start = after.len() - after.trim_start().len();
+        after[start..]
+            .find(char::is_whitespace)
+            .map_or(self.buffer.len(), |i| self.cursor + start + i)
+    }
+
+    /// Redraws the line in place and puts the terminal cursor on the editing cursor.
+    pub fn render(&mut self, stdout: &mut impl Write) -> io::Result<()> {
+        let (columns, _) = terminal::size()?;
+        let columns = columns.max(1) as usize;
+
+        if self.cursor_row > 0 {
+            queue!(stdout, cursor::MoveUp(self.cursor_row))?;
+        }
+        queue!(
+            stdout,
+            cursor::MoveToColumn(self.start_column),
+            terminal::Clear(ClearType::UntilNewLine)
+        )?;
+        for _ in 1..self.rows {
+            queue!(
+                stdout,
+                cursor::MoveToNextLine(1),
+                terminal::Clear(ClearType::CurrentLine)
+            )?;
+        }
+        if self.rows > 1 {
+            queue!(
+                stdout,
+                cursor::MoveUp(self.rows - 1),
+                cursor::MoveToColumn(self.start_column)
+            )?;
+        }
+
+        // Lay the graphemes out the way the terminal wraps them: a grapheme that does
+        // not fit on the row moves to the next one whole.
+        let (mut row, mut column) = (0, self.start_column as usize);
+        let mut cursor_at = None;
+        for (i, grapheme) in self.buffer.grapheme_indices(true) {
+            let width = grapheme.width();
+            if column + width > columns {
+                row += 1;
+                column = 0;
+            }
+            if i == self.cursor {
+                cursor_at = Some((row, column));
+            }
+            column += width;
+        }
+        queue!(stdout, Print(&self.buffer))?;
+        if column >= columns {
+            // The terminal holds the cursor on the last column until the next character,
+            // so print one to move it onto a fresh row.
+            queue!(stdout, Print(" \r"))?;
+            row += 1;
+            column = 0;
+        }
+        let (cursor_row, cursor_column) = cursor_at.unwrap_or((row, column));
+
+        if row > cursor_row {
+            queue!(stdout, cursor::MoveUp((row - cursor_row) as u16))?;
+        }
+        queue!(stdout, cursor::MoveToColumn(cursor_column as u16))?;
+        self.rows = row as u16 + 1;
+        self.cursor_row = cursor_row as u16;
+        self.cursor_column = cursor_column as u16;
+        stdout.flush()
+    }
+
+    /// Runs `f` with the terminal cursor on the first row of the line, so output placed
+    /// relative to the prompt lands in the same spot however far the line wraps.
+    pub fn on_first_row<T>(
+        &self,
+        stdout: &mut impl Write,
+        f: impl FnOnce(&str) -> io::Result<T>,
+    ) -> io::Result<T> {
+        if self.cursor_row > 0 {
+            execute!(stdout, cursor::MoveUp(self.cursor_row))?;
+        }
+        let result = f(&self.buffer)?;
+        if self.cursor_row > 0 {
+            queue!(stdout, cursor::MoveDown(self.cursor_row))?;
+        }
+        execute!(stdout, cursor::MoveToColumn(self.cursor_column))?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,389 @@
+use std::{fmt, io};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::commit::{
+    config::Config,
+    git::log_messages,
+    message::{message_file_lines, parse_header, Footer, BREAKING_CHANGE_TOKENS},
+    rules::keeps_layout,
+};
+
+const IGNORED_PREFIXES: [&str; 5] = ["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitReport {
+    pub sha: String,
+    pub header: String,
+    pub violations: Vec<Violation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Junit,
+}
+
+impl Violation {
+    fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Violation {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Lints a message as it was committed, e.g. from `git log`.
+pub fn lint_message(config: &Config, raw: &str) -> Vec<Violation> {
+    lint_lines(config, message_lines(raw, false))
+}
+
+/// Lints a message file that git has not cleaned up yet, as a commit-msg hook sees it:
+/// `#` comment lines and everything below the scissors line are skipped.
+pub fn lint_message_file(config: &Config, raw: &str) -> Vec<Violation> {
+    lint_lines(config, message_lines(raw, true))
+}
+
+fn lint_lines(config: &Config, lines: Vec<(usize, &str)>) -> Vec<Violation> {
+    let Some(&(header_line, header)) = lines.first() else {
+        return vec![Violation::new(1, 1, "commit message is empty")];
+    };
+    if IGNORED_PREFIXES.iter().any(|p| header.starts_with(p)) {
+        return Vec::new();
+    }
+
+    let mut violations = lint_header(config, header_line, header);
+
+    if let Some(&(line, text)) = lines.get(1) {
+        if !text.trim().is_empty() {
+            violations.push(Violation::new(
+                line,
+                1,
+                "body must be separated from the header by a blank line",
+            ));
+        }
+    }
+
+    // Lines the body wrapper leaves alone, and footers such as a long `Signed-off-by`,
+    // are not held to the body line length.
+    let body = &lines[1..];
+    let footers = footer_block_start(body);
+    let mut in_fence = false;
+    for (index, &(line, text)) in body.iter().enumerate() {
+        if index < footers && !keeps_layout(text, &mut in_fence) {
+            if let Some(error) = config.rules.check_body_line(text) {
+                violations.push(Violation::new(
+                    line,
+                    config.rules.body_max_line_length + 1,
+                    error,
+                ));
+            }
+        }
+        for token in BREAKING_CHANGE_TOKENS {
+            // Only the whole footer token counts: `BREAKING CHANGES: …` is body text.
+            let Some(rest) = text.strip_prefix(token) else {
+                continue;
+            };
+            if !rest.starts_with(':') && !rest.starts_with(" #") {
+                continue;
+            }
+            if !rest.starts_with(": ") || rest[2..].trim().is_empty() {
+                violations.push(Violation::new(
+                    line,
+                    token.len() + 1,
+                    format!("`{}` must be followed by \": \" and a description", token),
+                ));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Index of the first footer line in `body`, or `body.len()` when there are none. As in
+/// `ConventionalCommit::parse`, footers are a last paragraph that starts with one.
+fn footer_block_start(body: &[(usize, &str)]) -> usize {
+    let start = body
+        .iter()
+        .rposition(|(_, text)| text.is_empty())
+        .map_or(0, |i| i + 1);
+    match body.get(start) {
+        Some(&(_, text)) if Footer::parse_line(text).is_some() => start,
+        _ => body.len(),
+    }
+}
+
+pub fn lint_range(config: &Config, from: Option<&str>, to: &str) -> io::Result<Vec<CommitReport>> {
+    Ok(log_messages(from, to)?
+        .into_iter()
+        .map(|commit| CommitReport {
+            header: commit
+                .message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            violations: lint_message(config, &commit.message),
+            sha: commit.sha,
+        })
+        .collect())
+}
+
+impl ReportFormat {
+    pub fn render(self, reports: &[CommitReport]) -> String {
+        match self {
+            ReportFormat::Text => render_text(reports),
+            ReportFormat::Json => serde_json::to_string_pretty(reports).unwrap_or_default() + "\n",
+            ReportFormat::Junit => render_junit(reports),
+        }
+    }
+}
+
+fn render_text(reports: &[CommitReport]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        let mark = if report.violations.is_empty() {
+            "✔"
+        } else {
+            "✖"
+        };
+        out.push_str(&format!(
+            "{} {} {}\n",
+            mark,
+            short_sha(&report.sha),
+            report.header
+        ));
+        for violation in &report.violations {
+            out.push_str(&format!("    {}\n", violation));
+        }
+    }
+
+    let failed = reports.iter().filter(|r| !r.violations.is_empty()).count();
+    out.push_str(&format!(
+        "\n{} commit(s) checked, {} with violations\n",
+        reports.len(),
+        failed
+    ));
+    out
+}
+
+fn render_junit(reports: &[CommitReport]) -> String {
+    let failed = reports.iter().filter(|r| !r.violations.is_empty()).count();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"commitz\" tests=\"{0}\" failures=\"{1}\">\n  <testsuite name=\"commitz lint\" tests=\"{0}\" failures=\"{1}\">\n",
+        reports.len(),
+        failed
+    ));
+
+    for report in reports {
+        let name = xml_escape(&format!("{} {}", short_sha(&report.sha), report.header));
+        if report.violations.is_empty() {
+            out.push_str(&format!(
+                "    <testcase classname=\"commitz.lint\" name=\"{}\"/>\n",
+                name
+            ));
+            continue;
+        }
+
+        let details: Vec<String> = report.violations.iter().map(|v| v.to_string()).collect();
+        out.push_str(&format!(
+            "    <testcase classname=\"commitz.lint\" name=\"{}\">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+            name,
+            xml_escape(&report.violations[0].message),
+            xml_escape(&details.join("\n"))
+        ));
+    }
+
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn lint_header(config: &Config, line: usize, header: &str) -> Vec<Violation> {
+    let column = |offset: usize| header[..offset].chars().count() + 1;
+    let commit = match parse_header(header) {
+        Ok(commit) => commit,
+        Err(e) => return vec![Violation::new(line, column(e.offset), e.error.to_string())],
+    };
+
+    let mut violations = Vec::new();
+    if !config.types.iter().any(|t| t.key == commit.commit_type) {
+        let allowed: Vec<&str> = config.types.iter().map(|t| t.key.as_str()).collect();
+        violations.push(Violation::new(
+            line,
+            1,
+            format!(
+                "type `{}` is not one of: {}",
+                commit.commit_type,
+                allowed.join(", ")
+            ),
+        ));
+    }
+
+    let after_type = commit.commit_type.len();
+    match &commit.scope {
+        Some(scope) if !config.scope_allowed(scope) => violations.push(Violation::new(
+            line,
+            column(after_type + 1),
+            format!("scope `{}` is not in the configured scopes", scope),
+        )),
+        None if config.scope_required => violations.push(Violation::new(
+            line,
+            column(after_type),
+            "scope is required",
+        )),
+        _ => {}
+    }
+
+    let subject = &commit.description;
+    let subject_column = column(header.len() - subject.trim_start().len());
+    for error in config.rules.check_subject(subject) {
+        violations.push(Violation::new(line, subject_column, error));
+    }
+    if let Some(error) = config.rules.check_header_length(header) {
+        violations.push(Violation::new(
+            line,
+            config.rules.header_max_length + 1,
+            error,
+        ));
+    }
+
+    violations
+}
+
+fn message_lines(raw: &str, strip_comments: bool) -> Vec<(usize, &str)> {
+    let numbered: Vec<(usize, &str)> = if strip_comments {
+        message_file_lines(raw).collect()
+    } else {
+        raw.lines()
+            .enumerate()
+            .map(|(i, text)| (i + 1, text))
+            .collect()
+    };
+    let mut lines: Vec<(usize, &str)> = numbered
+        .into_iter()
+        .skip_while(|(_, text)| text.trim().is_empty())
+        .map(|(line, text)| (line, text.trim_end()))
+        .collect();
+
+    while lines.last().is_some_and(|(_, text)| text.is_empty()) {
+        lines.pop();
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commit::{
+        answers::Answers,
+        message::{ConventionalCommit, SCISSORS},
+    };
+
+    fn positions(violations: &[Violation]) -> Vec<(usize, usize)> {
+        violations.iter().map(|v| (v.line, v.column)).collect()
+    }
+
+    #[test]
+    fn header_columns() {
+        let config = Config::default();
+        assert_eq!(
+            positions(&lint_message(&config, "feat:add x")),
+            vec![(1, 6)]
+        );
+        assert_eq!(
+            positions(&lint_message(&config, "feat(api: add x")),
+            vec![(1, 5)]
+        );
+        assert_eq!(
+            positions(&lint_message(&config, "feat(api): add x.")),
+            vec![(1, 12)]
+        );
+        assert_eq!(
+            positions(&lint_message(&config, "\n\nnope: add x")),
+            vec![(3, 1)]
+        );
+    }
+
+    #[test]
+    fn ignored_prefixes() {
+        let config = Config::default();
+        for header in [
+            "Merge branch 'main' into feature",
+            "Revert \"feat: add x\"",
+            "fixup! feat: add x",
+            "squash! feat: add x",
+            "amend! feat: add x",
+        ] {
+            assert_eq!(lint_message(&config, header), Vec::new(), "{}", header);
+        }
+    }
+
+    #[test]
+    fn message_file_stops_at_scissors() {
+        let config = Config::default();
+        let message = format!(
+            "# Please enter the commit message\nfeat: add x\n\nbody\n# {}\n{}\n",
+            SCISSORS,
+            "diff --git ".repeat(20)
+        );
+        assert_eq!(lint_message_file(&config, &message), Vec::new());
+    }
+
+    #[test]
+    fn body_line_length() {
+        let config = Config::default();
+        let long = "word ".repeat(20);
+        let message = format!(
+            "feat: add x\n\n{0}\n\n- {0}\n    {0}\n```\n{0}\n```\n\nSigned-off-by: {0}",
+            long.trim()
+        );
+        assert_eq!(positions(&lint_message(&config, &message)), vec![(3, 73)]);
+    }
+
+    #[test]
+    fn rendered_messages_pass() {
+        let config = Config::default();
+        let long = "word ".repeat(20);
+        let answers = Answers {
+            commit_type: Some("feat".to_string()),
+            subject: Some("add x".to_string()),
+            body: Some(format!("{0}\n\n- {0}", long.trim())),
+            breaking: Some(true),
+            breaking_note: Some(long.trim().to_string()),
+            ..Default::default()
+        };
+        let message = ConventionalCommit::from(&answers.render(&config)).to_string();
+        assert_eq!(lint_message(&config, &message), Vec::new());
+    }
+}
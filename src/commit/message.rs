@@ -0,0 +1,437 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::commit::types::RenderCommit;
+
+pub const BREAKING_CHANGE_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+/// The comment line `git commit --verbose` puts above the diff.
+pub const SCISSORS: &str = "------------------------ >8 ------------------------";
+/// Footer tokens that reference issues, compared case-insensitively.
+pub const ISSUE_TOKENS: [&str; 12] = [
+    "Close",
+    "Closes",
+    "Closed",
+    "Fix",
+    "Fixes",
+    "Fixed",
+    "Resolve",
+    "Resolves",
+    "Resolved",
+    "Ref",
+    "Refs",
+    "References",
+];
+
+/// A parsed Conventional Commits message.
+///
+/// `parse` keeps the body and footer text verbatim, so formatting the result with
+/// `Display` reproduces the original message (minus trailing whitespace).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub breaking_marker: bool,
+    pub description: String,
+    pub body: Option<String>,
+    pub footers: Vec<Footer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footer {
+    pub token: String,
+    pub separator: String,
+    pub value: String,
+}
+
+/// A `ParseError` in a header, with the byte offset it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderError {
+    pub error: ParseError,
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    MissingType,
+    UnclosedScope,
+    EmptyScope,
+    MissingSeparator,
+    EmptyDescription,
+    MissingBlankLine,
+}
+
+impl ConventionalCommit {
+    pub fn parse(message: &str) -> Result<ConventionalCommit, ParseError> {
+        let message = message.replace("\r\n", "\n");
+        let message = message.trim_end();
+        if message.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let (header, rest) = match message.split_once('\n') {
+            Some((header, rest)) => (header, Some(rest)),
+            None => (message, None),
+        };
+        let mut commit = parse_header(header).map_err(|e| e.error)?;
+
+        let Some(rest) = rest else {
+            return Ok(commit);
+        };
+        let Some(rest) = rest.strip_prefix('\n') else {
+            return Err(ParseError::MissingBlankLine);
+        };
+
+        let (body, footers) = split_footers(rest);
+        commit.body = body.map(str::to_string);
+        commit.footers = footers;
+        Ok(commit)
+    }
+
+    pub fn header(&self) -> String {
+        let scope = self
+            .scope
+            .as_ref()
+            .map(|s| format!("({})", s))
+            .unwrap_or_default();
+        let marker = if self.breaking_marker { "!" } else { "" };
+
+        format!(
+            "{}{}{}: {}",
+            self.commit_type, scope, marker, self.description
+        )
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_marker || !self.breaking_notes().is_empty()
+    }
+
+    pub fn breaking_notes(&self) -> Vec<&str> {
+        self.footers
+            .iter()
+            .filter(|f| f.is_breaking_change())
+            .map(|f| f.value.as_str())
+            .collect()
+    }
+
+    pub fn footer(&self, token: &str) -> Option<&Footer> {
+        self.footers
+            .iter()
+            .find(|f| f.token.eq_ignore_ascii_case(token))
+    }
+}
+
+impl Footer {
+    pub fn new(token: &str, separator: &str, value: &str) -> Self {
+        Footer {
+            token: token.to_string(),
+            separator: separator.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn parse_line(line: &str) -> Option<Footer> {
+        for token in BREAKING_CHANGE_TOKENS {
+            if let Some(value) = line
+                .strip_prefix(token)
+                .and_then(|rest| rest.strip_prefix(": "))
+            {
+                return Some(Footer::new(token, ": ", value));
+            }
+        }
+
+        [": ", " #"].into_iter().find_map(|separator| {
+            let (token, value) = line.split_once(separator)?;
+            let valid_token =
+                !token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            valid_token.then(|| Footer::new(token, separator, value))
+        })
+    }
+
+    pub fn is_breaking_change(&self) -> bool {
+        BREAKING_CHANGE_TOKENS.contains(&self.token.as_str())
+    }
+
+    /// Whether the footer points at issues, e.g. `Closes #12` or `Refs: #3, #4`.
+    pub fn is_issue_reference(&self) -> bool {
+        !self.is_breaking_change()
+            && (self.separator == " #"
+                || ISSUE_TOKENS
+                    .iter()
+                    .any(|token| token.eq_ignore_ascii_case(&self.token)))
+    }
+}
+
+impl fmt::Display for ConventionalCommit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.header())?;
+
+        if let Some(body) = &self.body {
+            write!(f, "\n\n{}", body)?;
+        }
+        if !self.footers.is_empty() {
+            let footers: Vec<String> = self.footers.iter().map(|f| f.to_string()).collect();
+            write!(f, "\n\n{}", footers.join("\n"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Footer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.token, self.separator, self.value)
+    }
+}
+
+impl FromStr for ConventionalCommit {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ConventionalCommit::parse(s)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParseError::Empty => "commit message is empty",
+            ParseError::MissingType => "header must start with a type",
+            ParseError::UnclosedScope => "scope is missing a closing `)`",
+            ParseError::EmptyScope => "scope must not be empty",
+            ParseError::MissingSeparator => "type and scope must be followed by \": \"",
+            ParseError::EmptyDescription => "description must not be empty",
+            ParseError::MissingBlankLine => {
+                "body must be separated from the header by a blank line"
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<&RenderCommit> for ConventionalCommit {
+    fn from(parts: &RenderCommit) -> Self {
+        let scope = parts
+            .final_scope
+            .trim_start_matches('(')
+            .trim_end_matches(')');
+
+        let mut footers = Vec::new();
+        if !parts.breaking_changes.is_empty() {
+            footers.push(Footer::new(
+                "BREAKING CHANGE",
+                ": ",
+                &parts.breaking_changes,
+            ));
+        }
+        if !parts.issue_prefix.is_empty() {
+            let line = format!("{} {}", parts.issue_prefix.trim(), parts.issue_refs);
+            footers.push(
+                Footer::parse_line(&line).unwrap_or_else(|| {
+                    Footer::new(parts.issue_prefix.trim(), " ", &parts.issue_refs)
+                }),
+            );
+        }
+
+        footers.extend(
+            parts
+                .extra_footers
+                .iter()
+                .filter_map(|line| Footer::parse_line(line)),
+        );
+
+        ConventionalCommit {
+            commit_type: parts.chosen_type.clone(),
+            scope: (!scope.is_empty()).then(|| scope.to_string()),
+            breaking_marker: parts.breaking_marker,
+            description: parts.desc.clone(),
+            body: (!parts.longer_description.is_empty()).then(|| parts.longer_description.clone()),
+            footers,
+        }
+    }
+}
+
+/// Parses a header line on its own. On failure the error carries the byte offset in
+/// `header` where parsing stopped, so `lint` can point at the column.
+pub fn parse_header(header: &str) -> Result<ConventionalCommit, HeaderError> {
+    let error = |error, rest: &str| HeaderError {
+        error,
+        offset: header.len() - rest.len(),
+    };
+
+    let type_end = header.find(['(', '!', ':']).unwrap_or(header.len());
+    let commit_type = &header[..type_end];
+    if commit_type.is_empty() || commit_type.contains(char::is_whitespace) {
+        return Err(error(ParseError::MissingType, header));
+    }
+
+    let mut rest = &header[type_end..];
+    let mut scope = None;
+    if let Some(after_paren) = rest.strip_prefix('(') {
+        let close = after_paren
+            .find(')')
+            .ok_or_else(|| error(ParseError::UnclosedScope, rest))?;
+        if after_paren[..close].trim().is_empty() {
+            return Err(error(ParseError::EmptyScope, after_paren));
+        }
+        scope = Some(after_paren[..close].to_string());
+        rest = &after_paren[close + 1..];
+    }
+
+    let breaking_marker = rest.starts_with('!');
+    if breaking_marker {
+        rest = &rest[1..];
+    }
+
+    let after_colon = rest
+        .strip_prefix(':')
+        .ok_or_else(|| error(ParseError::MissingSeparator, rest))?;
+    if after_colon.trim().is_empty() {
+        return Err(error(ParseError::EmptyDescription, after_colon));
+    }
+    let description = after_colon
+        .strip_prefix(' ')
+        .ok_or_else(|| error(ParseError::MissingSeparator, after_colon))?;
+
+    Ok(ConventionalCommit {
+        commit_type: commit_type.to_string(),
+        scope,
+        breaking_marker,
+        description: description.to_string(),
+        body: None,
+        footers: Vec::new(),
+    })
+}
+
+/// The lines git keeps from a commit message file, numbered from 1: `#` comment lines
+/// are dropped and everything from the scissors line on is cut off.
+pub fn message_file_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .take_while(|(_, line)| !(line.starts_with('#') && line.contains(SCISSORS)))
+        .filter(|(_, line)| !line.starts_with('#'))
+        .map(|(index, line)| (index + 1, line))
+}
+
+fn split_footers(text: &str) -> (Option<&str>, Vec<Footer>) {
+    if let Some(footers) = parse_footer_block(text) {
+        return (None, footers);
+    }
+
+    if let Some(index) = text.rfind("\n\n") {
+        if let Some(footers) = parse_footer_block(&text[index + 2..]) {
+            return (Some(&text[..index]), footers);
+        }
+    }
+
+    (Some(text), Vec::new())
+}
+
+fn parse_footer_block(block: &str) -> Option<Vec<Footer>> {
+    if block.contains("\n\n") {
+        return None;
+    }
+
+    let mut footers: Vec<Footer> = Vec::new();
+
+    for line in block.split('\n') {
+        match Footer::parse_line(line) {
+            Some(footer) => footers.push(footer),
+            None => {
+                let last = footers.last_mut()?;
+                last.value.push('\n');
+                last.value.push_str(line);
+            }
+        }
+    }
+
+    Some(footers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: &str) -> ConventionalCommit {
+        let commit = ConventionalCommit::parse(message).unwrap();
+        assert_eq!(commit.to_string(), message);
+        commit
+    }
+
+    #[test]
+    fn header_only() {
+        let commit = round_trip("feat(api)!: add login endpoint");
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert!(commit.breaking_marker);
+        assert_eq!(commit.description, "add login endpoint");
+        assert_eq!(commit.body, None);
+        assert!(commit.footers.is_empty());
+    }
+
+    #[test]
+    fn multi_paragraph_body() {
+        let commit =
+            round_trip("fix: handle empty input\n\nFirst paragraph\nwrapped.\n\nSecond one.");
+        assert_eq!(
+            commit.body.as_deref(),
+            Some("First paragraph\nwrapped.\n\nSecond one.")
+        );
+        assert!(commit.footers.is_empty());
+    }
+
+    #[test]
+    fn footers_with_continuation_lines() {
+        let commit = round_trip(
+            "feat: drop v1\n\nBody.\n\nBREAKING CHANGE: v1 tokens\n  are rejected now\nRefs #12\nReviewed-by: Z",
+        );
+        assert_eq!(commit.body.as_deref(), Some("Body."));
+        assert_eq!(
+            commit.footers,
+            vec![
+                Footer::new("BREAKING CHANGE", ": ", "v1 tokens\n  are rejected now"),
+                Footer::new("Refs", " #", "12"),
+                Footer::new("Reviewed-by", ": ", "Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn marker_and_breaking_change_footer() {
+        let commit =
+            round_trip("refactor(core)!: rename config\n\nBREAKING-CHANGE: `foo` is now `bar`");
+        assert!(commit.breaking_marker);
+        assert!(commit.is_breaking());
+        assert_eq!(commit.breaking_notes(), vec!["`foo` is now `bar`"]);
+    }
+
+    #[test]
+    fn last_paragraph_that_looks_like_footers() {
+        let commit = round_trip("docs: explain setup\n\nIntro.\n\nNote: run it twice\nSee #4");
+        assert_eq!(commit.body.as_deref(), Some("Intro."));
+        assert_eq!(
+            commit.footers,
+            vec![
+                Footer::new("Note", ": ", "run it twice"),
+                Footer::new("See", " #", "4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn message_file_comments_and_scissors() {
+        let text = format!("# header\nfeat: x\n\n# note\nbody\n# {}\ndiff\n", SCISSORS);
+        assert_eq!(
+            message_file_lines(&text).collect::<Vec<_>>(),
+            vec![(2, "feat: x"), (3, ""), (5, "body")]
+        );
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        let commit = ConventionalCommit::parse("feat: x\r\n\r\nbody\r\n").unwrap();
+        assert_eq!(commit.body.as_deref(), Some("body"));
+        assert_eq!(commit.to_string(), "feat: x\n\nbody");
+    }
+}
@@ -1,12 +1,42 @@
 use std::io::{self, stdout, Write};
 
 use crossterm::{
+    cursor,
     event::{self, Event, KeyCode},
-    execute,
+    execute, queue,
     style::{Color, Print, ResetColor, SetForegroundColor},
     terminal,
 };
 
+use unicode_width::UnicodeWidthChar;
+
+use crate::commit::line_editor::LineEditor;
+
+/// What a prompt produced: an answer, or a request to return to the previous step
+/// (Shift+Tab or Esc).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input<T> {
+    Submitted(T),
+    Back,
+}
+
+impl<T> Input<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Input<U> {
+        match self {
+            Input::Submitted(value) => Input::Submitted(f(value)),
+            Input::Back => Input::Back,
+        }
+    }
+}
+
+/// What a prompt that can hand its text over to an external editor produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptInput {
+    Text(Input<String>),
+    /// Ctrl+E was pressed, with the text typed so far.
+    Editor(String),
+}
+
 pub fn move_down(
     selected: &mut usize,
     cursor: &mut usize,
@@ -43,30 +73,47 @@ pub fn move_up(
     }
 }
 
+pub fn select_index(
+    selected: &mut usize,
+    cursor: &mut usize,
+    offset: &mut usize,
+    len: usize,
+    window_size: usize,
+    index: usize,
+) {
+    while *selected != index % len {
+        move_down(selected, cursor, offset, len, window_size);
+    }
+}
+
+/// Leaves the prompt screen and exits after Ctrl+C.
+fn abort() -> ! {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(
+        stdout(),
+        event::DisableBracketedPaste,
+        terminal::LeaveAlternateScreen,
+        SetForegroundColor(Color::Red),
+        Print("\n❌ Aborted by user (Ctrl+C).\n"),
+        ResetColor
+    );
+    std::process::exit(1);
+}
+
 pub fn handle_input(
     selected: &mut usize,
     cursor: &mut usize,
     offset: &mut usize,
     len: usize,
     window_size: usize,
-) -> io::Result<Option<usize>> {
+) -> io::Result<Option<Input<usize>>> {
     if let Event::Key(event) = event::read()? {
         match event.code {
             KeyCode::Down => move_down(selected, cursor, offset, len, window_size),
             KeyCode::Up => move_up(selected, cursor, offset, len, window_size),
-            KeyCode::Enter => return Ok(Some(*selected)),
-            KeyCode::Esc => {}
-            KeyCode::Char('c') if event.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                terminal::disable_raw_mode()?;
-                execute!(
-                    stdout(),
-                    terminal::LeaveAlternateScreen,
-                    SetForegroundColor(Color::Red),
-                    Print("\n❌ Aborted by user (Ctrl+C).\n"),
-                    ResetColor
-                )?;
-                std::process::exit(1);
-            }
+            KeyCode::Enter => return Ok(Some(Input::Submitted(*selected))),
+            KeyCode::Esc | KeyCode::BackTab => return Ok(Some(Input::Back)),
+            KeyCode::Char('c') if event.modifiers.contains(event::KeyModifiers::CONTROL) => abort(),
             _ => {}
         }
     }
@@ -74,49 +121,334 @@ pub fn handle_input(
 }
 
 pub fn handle_prompt_input() -> io::Result<String> {
-    let mut buffer = String::new();
+    Ok(match edit_prompt_input("", |_| Ok(()))? {
+        Input::Submitted(input) => input,
+        Input::Back => String::new(),
+    })
+}
+
+/// Edits a single line with the cursor keys, word motions (Alt+B/F, Ctrl+W) and paste.
+/// `on_change` runs after every change, with the cursor on the first row of the line.
+pub fn edit_prompt_input(
+    initial: &str,
+    mut on_change: impl FnMut(&str) -> io::Result<()>,
+) -> io::Result<Input<String>> {
     let mut stdout = io::stdout();
 
     terminal::enable_raw_mode()?;
-    loop {
-        if let Event::Key(event) = event::read()? {
-            match event.code {
+    let mut editor = LineEditor::new(initial)?;
+    execute!(stdout, event::EnableBracketedPaste)?;
+    editor.render(&mut stdout)?;
+    editor.on_first_row(&mut stdout, &mut on_change)?;
+    let input = loop {
+        match event::read()? {
+            Event::Key(event) => match event.code {
                 KeyCode::Char('c') if event.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                    terminal::disable_raw_mode()?;
-                    execute!(
-                        stdout,
-                        terminal::LeaveAlternateScreen,
-                        SetForegroundColor(Color::Red),
-                        Print("\n❌ Aborted by user (Ctrl+C).\n"),
-                        ResetColor
-                    )?;
-                    std::process::exit(1);
-                }
-                KeyCode::Char(c) => {
-                    buffer.push(c);
-                    write!(stdout, "{}", c)?;
-                    stdout.flush()?;
-                }
-                KeyCode::Backspace => {
-                    if buffer.pop().is_some() {
-                        write!(stdout, "\x08 \x08")?;
-                        stdout.flush()?;
-                    }
+                    abort()
                 }
                 KeyCode::Enter => {
-                    write!(stdout, "")?;
-                    stdout.flush()?;
-                    break;
+                    editor.handle_key(KeyCode::End.into());
+                    editor.render(&mut stdout)?;
+                    break Input::Submitted(editor.text().to_string());
                 }
-                KeyCode::Esc => {
-                    buffer.clear();
-                    break;
+                KeyCode::Esc | KeyCode::BackTab => break Input::Back,
+                _ if editor.handle_key(event) => {
+                    editor.render(&mut stdout)?;
+                    editor.on_first_row(&mut stdout, &mut on_change)?;
                 }
                 _ => {}
+            },
+            Event::Paste(text) => {
+                editor.insert(&text);
+                editor.render(&mut stdout)?;
+                editor.on_first_row(&mut stdout, &mut on_change)?;
             }
+            _ => {}
         }
-    }
+    };
+
+    execute!(stdout, event::DisableBracketedPaste)?;
+    terminal::disable_raw_mode()?;
+    Ok(input)
+}
+
+/// A multi-line text area: Enter starts a new line, Ctrl+D submits and Ctrl+E hands
+/// the text to an editor. Lines soft wrap
+/// at the terminal width, the area scrolls to keep the cursor on screen, and a ruler
+/// marks column `ruler` unless it is 0.
+pub fn edit_text_area(initial: &str, ruler: usize) -> io::Result<PromptInput> {
+    let mut area = TextArea::new(initial, ruler);
+    let mut stdout = io::stdout();
 
+    terminal::enable_raw_mode()?;
+    area.render(&mut stdout, false)?;
+    let input = loop {
+        let Event::Key(event) = event::read()? else {
+            continue;
+        };
+        let control = event.modifiers.contains(event::KeyModifiers::CONTROL);
+        match event.code {
+            KeyCode::Char('c') if control => abort(),
+            KeyCode::Char('d') if control => {
+                break PromptInput::Text(Input::Submitted(area.text()))
+            }
+            KeyCode::Char('e') if control => break PromptInput::Editor(area.text()),
+            KeyCode::Esc | KeyCode::BackTab => break PromptInput::Text(Input::Back),
+            KeyCode::Char(c) if !control => area.insert(c),
+            KeyCode::Enter => area.new_line(),
+            KeyCode::Backspace => area.backspace(),
+            KeyCode::Delete => area.delete(),
+            KeyCode::Left => area.left(),
+            KeyCode::Right => area.right(),
+            KeyCode::Up => area.up(),
+            KeyCode::Down => area.down(),
+            KeyCode::Home => area.col = 0,
+            KeyCode::End => area.col = area.lines[area.row].len(),
+            _ => continue,
+        }
+        area.render(&mut stdout, false)?;
+    };
+
+    area.render(&mut stdout, true)?;
     terminal::disable_raw_mode()?;
-    Ok(buffer)
+    Ok(input)
+}
+
+const TEXT_AREA_HINT: &str = "Enter: new line, Ctrl+D: submit, Ctrl+E: editor, Esc: back";
+
+struct TextArea {
+    lines: Vec<Vec<char>>,
+    row: usize,
+    col: usize,
+    ruler: usize,
+    /// Screen row of the terminal cursor, counted from the top of the area.
+    cursor_row: u16,
+    /// First wrapped row shown when the text is taller than the terminal.
+    scroll: usize,
+}
+
+impl TextArea {
+    fn new(initial: &str, ruler: usize) -> Self {
+        let mut lines: Vec<Vec<char>> = initial.lines().map(|l| l.chars().collect()).collect();
+        if lines.is_empty() {
+            lines.push(Vec::new());
+        }
+        let row = lines.len() - 1;
+        let col = lines[row].len();
+        TextArea {
+            lines,
+            row,
+            col,
+            ruler,
+            cursor_row: 0,
+            scroll: 0,
+        }
+    }
+
+    fn text(&self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| line.iter().collect::<String>().trim_end().to_string())
+            .collect();
+        lines.join("\n").trim_matches('\n').to_string()
+    }
+
+    fn insert(&mut self, c: char) {
+        self.lines[self.row].insert(self.col, c);
+        self.col += 1;
+    }
+
+    fn new_line(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.row += 1;
+        self.col = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            self.lines[self.row].remove(self.col);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.lines[self.row].remove(self.col);
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    fn up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.lines[self.row].len());
+        }
+    }
+
+    fn down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.lines[self.row].len());
+        }
+    }
+
+    /// Redraws the area in place. Only the wrapped rows around the cursor that fit on
+    /// the terminal are drawn. The `done` form drops the ruler and key hints, draws every
+    /// row and leaves the cursor below the text.
+    fn render(&mut self, stdout: &mut impl Write, done: bool) -> io::Result<()> {
+        let (columns, height) = terminal::size()?;
+        let width = (columns as usize).saturating_sub(2).max(10);
+        let ruler = (!done && self.ruler > 0 && self.ruler <= width).then_some(self.ruler);
+
+        let rows: Vec<(usize, usize, usize, &[char])> = self
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                wrap(line, width)
+                    .into_iter()
+                    .map(move |(start, column, chunk)| (row, start, column, chunk))
+            })
+            .collect();
+        let cursor_index = rows
+            .iter()
+            .rposition(|&(row, start, _, _)| row == self.row && start <= self.col)
+            .unwrap_or(0);
+        let visible = if done {
+            rows.len()
+        } else {
+            // Leave room for the ruler and the hint line.
+            (height as usize)
+                .saturating_sub(1 + usize::from(ruler.is_some()))
+                .max(1)
+        };
+        self.scroll = self
+            .scroll
+            .min(rows.len().saturating_sub(visible))
+            .clamp((cursor_index + 1).saturating_sub(visible), cursor_index);
+
+        if self.cursor_row > 0 {
+            queue!(stdout, cursor::MoveUp(self.cursor_row))?;
+        }
+        queue!(
+            stdout,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::FromCursorDown)
+        )?;
+
+        let mut screen_row = 0;
+        let mut cursor_at = (0, 0);
+        let mut last_row_width = 0;
+        if let Some(ruler) = ruler {
+            queue!(
+                stdout,
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!(" {}┐ {}\r\n", "─".repeat(ruler - 1), ruler)),
+            )?;
+            screen_row += 1;
+        }
+        for (index, &(_, start, column, chunk)) in
+            rows.iter().enumerate().skip(self.scroll).take(visible)
+        {
+            queue!(stdout, Print(" "))?;
+            let mut row_width = 0;
+            for &c in chunk {
+                let color = match ruler {
+                    Some(ruler) if column + row_width >= ruler => Color::Yellow,
+                    _ => Color::DarkGreen,
+                };
+                queue!(stdout, SetForegroundColor(color), Print(c))?;
+                row_width += c.width().unwrap_or(0);
+            }
+            queue!(stdout, Print("\r\n"))?;
+            last_row_width = row_width;
+            if index == cursor_index {
+                let before: usize = chunk[..self.col - start]
+                    .iter()
+                    .map(|c| c.width().unwrap_or(0))
+                    .sum();
+                cursor_at = (screen_row, before);
+            }
+            screen_row += 1;
+        }
+
+        if done {
+            queue!(
+                stdout,
+                ResetColor,
+                cursor::MoveUp(1),
+                cursor::MoveToColumn(1 + last_row_width as u16)
+            )?;
+            self.cursor_row = 0;
+            return stdout.flush();
+        }
+
+        queue!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            Print(
+                TEXT_AREA_HINT
+                    .chars()
+                    .take(columns.saturating_sub(1) as usize)
+                    .collect::<String>()
+            ),
+            ResetColor,
+            cursor::MoveUp(screen_row - cursor_at.0),
+            cursor::MoveToColumn(1 + cursor_at.1 as u16),
+            SetForegroundColor(Color::DarkGreen),
+        )?;
+        self.cursor_row = cursor_at.0;
+        stdout.flush()
+    }
+}
+
+/// Splits a line into screen rows at most `width` columns wide, with the index of each
+/// row's first character and the display column it starts at. A wide character that
+/// does not fit moves to the next row whole. There is always a row after a full one,
+/// for the cursor.
+fn wrap(line: &[char], width: usize) -> Vec<(usize, usize, &[char])> {
+    let mut rows = Vec::new();
+    let (mut start, mut column, mut row_width) = (0, 0, 0);
+    for (i, c) in line.iter().enumerate() {
+        let char_width = c.width().unwrap_or(0);
+        if row_width + char_width > width {
+            rows.push((start, column, &line[start..i]));
+            start = i;
+            column += row_width;
+            row_width = 0;
+        }
+        row_width += char_width;
+    }
+    rows.push((start, column, &line[start..]));
+    if row_width >= width {
+        rows.push((line.len(), column + row_width, &line[line.len()..]));
+    }
+    rows
 }
@@ -8,134 +8,336 @@ use crossterm::{
 };
 
 use crate::commit::{
-    navigation::{handle_input, handle_prompt_input},
-    renderer::render_options,
-    types::CommitType,
+    answers::Answers,
+    config::{BreakingChangeStyle, Config},
+    editor::edit_text,
+    navigation::{
+        edit_prompt_input, edit_text_area, handle_input, select_index, Input, PromptInput,
+    },
+    renderer::{render_options, render_scope},
+    rules::Rules,
+    types::{CommitType, Scope},
 };
 
-pub fn read_desc() -> io::Result<String> {
+const TEXT_FILE: &str = "COMMITZ_TEXT";
+
+pub fn read_desc(config: &Config, header_prefix: &str, initial: &str) -> io::Result<Input<String>> {
     let mut stdout = stdout();
-    let mut error_printed = false;
-    let mut input = String::new();
+    let rules = &config.rules;
+    let mut input = initial.to_string();
 
     execute!(
         stdout,
         SetForegroundColor(Color::DarkGreen),
         Print("? "),
         ResetColor,
-        Print("Write a SHORT, IMPERATIVE tense description of the change:"),
-        ResetColor,
-        SetForegroundColor(Color::DarkGrey),
-        Print("\n [Infinity more chars allowed]\n "),
+        Print(&config.messages.subject),
         ResetColor,
+        Print("\n\n "),
         SetForegroundColor(Color::DarkGreen),
         cursor::SavePosition,
     )?;
     stdout.flush()?;
 
     loop {
-        input.clear();
-        input = handle_prompt_input()?;
+        input = match edit_prompt_input(&input, |buffer| {
+            render_char_counter(rules, &format!("{}{}", header_prefix, buffer.trim()))
+        })? {
+            Input::Submitted(input) => input,
+            Input::Back => return Ok(Input::Back),
+        };
         let trimmed = input.trim();
 
-        if !trimmed.is_empty() {
-            execute!(stdout, ResetColor)?;
-            return Ok(trimmed.to_string());
-        } else if !error_printed {
+        let header = format!("{}{}", header_prefix, trimmed);
+        let error = rules
+            .check_subject(trimmed)
+            .into_iter()
+            .next()
+            .or_else(|| rules.check_header_length(&header));
+
+        let Some(error) = error else {
             execute!(
                 stdout,
-                cursor::RestorePosition,
-                terminal::Clear(ClearType::CurrentLine),
-                cursor::MoveToColumn(0),
-                SetForegroundColor(Color::Red),
-                Print("\n>> [ERROR] input is required"),
                 ResetColor,
-                cursor::RestorePosition,
+                terminal::Clear(ClearType::FromCursorDown)
             )?;
-            stdout.flush()?;
-            error_printed = true;
-        } else {
-            execute!(stdout, cursor::RestorePosition)?;
-            stdout.flush()?;
-        }
+            return Ok(Input::Submitted(trimmed.to_string()));
+        };
+
+        execute!(
+            stdout,
+            cursor::MoveToNextLine(1),
+            terminal::Clear(ClearType::CurrentLine),
+            SetForegroundColor(Color::Red),
+            Print(format!(">> [ERROR] {}", error)),
+            ResetColor,
+            cursor::RestorePosition,
+            terminal::Clear(ClearType::UntilNewLine),
+            SetForegroundColor(Color::DarkGreen),
+        )?;
+        stdout.flush()?;
     }
 }
-pub fn read_multiline(prompt: &str) -> io::Result<String> {
+
+fn render_char_counter(rules: &Rules, header: &str) -> io::Result<()> {
     let mut stdout = stdout();
+    let (column, _) = cursor::position()?;
+    let (text, color) = match rules.remaining_header_chars(header) {
+        Some(remaining) if remaining < 0 => (
+            format!(" [{} chars over the limit]", -remaining),
+            Color::Red,
+        ),
+        Some(remaining) => (
+            format!(" [{} more chars allowed]", remaining),
+            Color::DarkGrey,
+        ),
+        None => (
+            " [Infinity more chars allowed]".to_string(),
+            Color::DarkGrey,
+        ),
+    };
+
     execute!(
         stdout,
+        cursor::MoveToPreviousLine(1),
+        terminal::Clear(ClearType::CurrentLine),
+        SetForegroundColor(color),
+        Print(text),
+        cursor::MoveToNextLine(1),
+        cursor::MoveToColumn(column),
         SetForegroundColor(Color::DarkGreen),
-        Print("\n? "),
-        ResetColor,
-        Print(prompt),
-        ResetColor,
-        SetForegroundColor(Color::DarkGrey),
-        Print(" (press Enter to skip):\n "),
-        ResetColor,
-        SetForegroundColor(Color::DarkGreen)
     )?;
-    stdout.flush()?;
+    stdout.flush()
+}
 
-    let input = handle_prompt_input()?;
+pub fn read_multiline(prompt: &str, initial: &str, ruler: usize) -> io::Result<Input<String>> {
+    let mut stdout = stdout();
+    let mut initial = initial.to_string();
 
-    Ok(input
-        .trim()
-        .split('|')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("\n"))
+    loop {
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGreen),
+            Print("\n? "),
+            ResetColor,
+            Print(prompt),
+            ResetColor,
+            SetForegroundColor(Color::DarkGrey),
+            Print(" (press Ctrl+D to skip):\n"),
+            ResetColor,
+            SetForegroundColor(Color::DarkGreen)
+        )?;
+        stdout.flush()?;
+
+        let typed = match edit_text_area(&initial, ruler)? {
+            PromptInput::Text(input) => return Ok(input),
+            PromptInput::Editor(typed) => typed,
+        };
+
+        match edit_multiline(prompt, &typed) {
+            Ok(text) => {
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::DarkGreen),
+                    Print("? "),
+                    ResetColor,
+                    Print(prompt.trim_end()),
+                    SetForegroundColor(Color::DarkGreen),
+                    Print(format!(" {}", text.replace('\n', " | "))),
+                    ResetColor
+                )?;
+                return Ok(Input::Submitted(text));
+            }
+            Err(e) => {
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::Red),
+                    Print(format!(">> [ERROR] {}", e)),
+                    ResetColor
+                )?;
+                initial = typed;
+            }
+        }
+    }
 }
 
-pub fn read_issues() -> io::Result<(String, String)> {
+/// Opens `text` in the editor below a commented copy of the prompt and returns what was
+/// saved, without the comments. Runs on the main screen, since editors take over the
+/// terminal themselves.
+fn edit_multiline(prompt: &str, text: &str) -> io::Result<String> {
     let mut stdout = stdout();
+    let hint = prompt
+        .trim()
+        .lines()
+        .map(|line| format!("# {}\n", line))
+        .collect::<String>();
+    let template = format!(
+        "{}\n\n{}# Lines starting with '#' will be ignored, and an empty text is skipped.\n",
+        text, hint
+    );
+
+    execute!(stdout, terminal::LeaveAlternateScreen)?;
+    let edited = edit_text(TEXT_FILE, &template);
     execute!(
         stdout,
-        SetForegroundColor(Color::DarkGreen),
-        Print("\n? "),
-        ResetColor,
-        Print("Select the ISSUES type of change (optional), Input ISSUES prefix\n"),
-        ResetColor,
-        SetForegroundColor(Color::DarkGrey),
-        Print(" (press Enter to skip):\n "),
-        ResetColor,
+        terminal::EnterAlternateScreen,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0)
     )?;
-    io::stdout().flush()?;
+    edited
+}
 
-    execute!(stdout, SetForegroundColor(Color::DarkGreen))?;
-    stdout.flush()?;
+pub fn read_yes_no(prompt: &str, default: bool) -> io::Result<Input<bool>> {
+    let mut stdout = stdout();
+    let hint = if default { " (Y/n) " } else { " (y/N) " };
 
-    let issue_prefix = handle_prompt_input()?;
+    loop {
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGreen),
+            Print("\n? "),
+            ResetColor,
+            Print(prompt),
+            SetForegroundColor(Color::DarkGrey),
+            Print(hint),
+            ResetColor,
+            SetForegroundColor(Color::DarkGreen)
+        )?;
+        stdout.flush()?;
 
-    let mut issue_refs = String::new();
+        let Input::Submitted(input) = edit_prompt_input("", |_| Ok(()))? else {
+            execute!(stdout, ResetColor)?;
+            return Ok(Input::Back);
+        };
+        execute!(stdout, ResetColor)?;
+
+        match input.trim().to_lowercase().as_str() {
+            "" => return Ok(Input::Submitted(default)),
+            "y" | "yes" => return Ok(Input::Submitted(true)),
+            "n" | "no" => return Ok(Input::Submitted(false)),
+            _ => {
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::Red),
+                    Print("\n>> [ERROR] please enter y or n"),
+                    ResetColor
+                )?;
+            }
+        }
+    }
+}
+
+pub fn read_breaking(
+    config: &Config,
+    initial_breaking: bool,
+    initial_note: &str,
+) -> io::Result<Input<(bool, String)>> {
+    let style = config.breaking_change_style;
+    let mut initial_breaking = initial_breaking;
+    let mut initial_note = initial_note.to_string();
+
+    'confirm: loop {
+        match read_yes_no(&config.messages.breaking_confirm, initial_breaking)? {
+            Input::Back => return Ok(Input::Back),
+            Input::Submitted(false) => return Ok(Input::Submitted((false, String::new()))),
+            Input::Submitted(true) if !style.uses_footer() => {
+                return Ok(Input::Submitted((true, String::new())))
+            }
+            Input::Submitted(true) => initial_breaking = true,
+        }
 
-    if !issue_prefix.is_empty() {
+        loop {
+            let note = match read_multiline(
+                &config.messages.breaking,
+                &initial_note,
+                config.rules.body_max_line_length,
+            )? {
+                Input::Submitted(note) => note,
+                Input::Back => continue 'confirm,
+            };
+            if !note.is_empty() || style == BreakingChangeStyle::Both {
+                return Ok(Input::Submitted((true, note)));
+            }
+            initial_note = note;
+
+            execute!(
+                stdout(),
+                SetForegroundColor(Color::Red),
+                Print("\n>> [ERROR] input is required"),
+                ResetColor
+            )?;
+        }
+    }
+}
+
+pub fn read_issues(config: &Config, initial: &str) -> io::Result<Input<(String, String)>> {
+    let mut stdout = stdout();
+    let (initial_prefix, initial_refs) = initial
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((initial.trim(), ""));
+    let mut issue_prefix = initial_prefix.to_string();
+
+    loop {
         execute!(
             stdout,
             SetForegroundColor(Color::DarkGreen),
             Print("\n? "),
             ResetColor,
-            Print("List any ISSUES AFFECTED by this change. E.g.: #31, #34: "),
+            Print(&config.messages.footer_prefix),
+            ResetColor,
+            SetForegroundColor(Color::DarkGrey),
+            Print(" (press Enter to skip):\n "),
             ResetColor,
+            SetForegroundColor(Color::DarkGreen)
         )?;
-        io::stdout().flush()?;
+        stdout.flush()?;
 
-        execute!(stdout, SetForegroundColor(Color::DarkGreen))?;
+        issue_prefix = match edit_prompt_input(&issue_prefix, |_| Ok(()))? {
+            Input::Submitted(prefix) => prefix,
+            Input::Back => return Ok(Input::Back),
+        };
+        if issue_prefix.is_empty() {
+            return Ok(Input::Submitted((issue_prefix, String::new())));
+        }
+
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGreen),
+            Print("\n? "),
+            ResetColor,
+            Print(&config.messages.footer),
+            ResetColor,
+            SetForegroundColor(Color::DarkGreen)
+        )?;
         stdout.flush()?;
 
-        issue_refs = handle_prompt_input()?.trim().to_string();
+        if let Input::Submitted(refs) = edit_prompt_input(initial_refs.trim(), |_| Ok(()))? {
+            return Ok(Input::Submitted((issue_prefix, refs.trim().to_string())));
+        }
     }
-
-    Ok((issue_prefix, issue_refs))
 }
-pub fn read_commit_type() -> io::Result<CommitType> {
+
+pub fn read_commit_type(config: &Config, initial: &str) -> io::Result<Input<CommitType>> {
     let mut stdout = stdout();
-    let types = CommitType::load();
+    let types = &config.types;
     let mut selected = 0;
     let mut offset = 0;
     let mut cursor = 0;
-    let window_size = 7;
+    let window_size = types.len().min(7);
 
+    if let Some(index) = types.iter().position(|t| t.key == initial) {
+        select_index(
+            &mut selected,
+            &mut cursor,
+            &mut offset,
+            types.len(),
+            window_size,
+            index,
+        );
+    }
+
+    terminal::enable_raw_mode()?;
     let chosen_type = loop {
         execute!(
             stdout,
@@ -144,20 +346,21 @@ pub fn read_commit_type() -> io::Result<CommitType> {
             SetForegroundColor(Color::DarkGreen),
             Print("? "),
             ResetColor,
-            Print("Select the type of change that you're committing: "),
+            Print(&config.messages.commit_type),
             ResetColor
         )?;
 
-        render_options(&mut stdout, &types, selected, offset, window_size)?;
+        render_options(&mut stdout, types, selected, offset, window_size)?;
 
-        if let Some(chosen) = handle_input(
+        match handle_input(
             &mut selected,
             &mut cursor,
             &mut offset,
             types.len(),
             window_size,
         )? {
-            break &types[chosen];
+            Some(Input::Submitted(chosen)) => break &types[chosen],
+            Some(Input::Back) | None => {}
         }
     };
 
@@ -167,7 +370,7 @@ pub fn read_commit_type() -> io::Result<CommitType> {
         cursor::MoveTo(0, 0),
         Print("? "),
         ResetColor,
-        Print("Select the type of change that you're committing: "),
+        Print(&config.messages.commit_type),
         ResetColor,
         SetForegroundColor(Color::DarkGreen),
         Print(format!(
@@ -178,31 +381,281 @@ pub fn read_commit_type() -> io::Result<CommitType> {
         ResetColor,
     )?;
 
-    Ok(CommitType::new(&chosen_type.key, &chosen_type.description))
+    Ok(Input::Submitted(CommitType::new(
+        &chosen_type.key,
+        &chosen_type.description,
+    )))
 }
-pub fn read_scope(chosen_scope: &str) -> io::Result<String> {
+
+pub fn read_scope(config: &Config, chosen_scope: &str, initial: &str) -> io::Result<Input<String>> {
     let mut stdout = stdout();
-    let final_scope = if chosen_scope == "custom" {
-        execute!(
-            stdout,
-            SetForegroundColor(Color::DarkGreen),
-            Print("? "),
-            ResetColor,
-            Print("Denote the SCOPE of this change: "),
-            SetForegroundColor(Color::DarkGrey),
-            ResetColor,
-            SetForegroundColor(Color::DarkGreen),
-            SavePosition,
-            terminal::Clear(ClearType::FromCursorDown)
-        )?;
-        stdout.flush()?;
-        let custom_scope = handle_prompt_input()?;
+    let final_scope = match chosen_scope {
+        Scope::CUSTOM => {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::DarkGreen),
+                Print("? "),
+                ResetColor,
+                Print(&config.messages.custom_scope),
+                SetForegroundColor(Color::DarkGrey),
+                ResetColor,
+                SetForegroundColor(Color::DarkGreen),
+                SavePosition,
+                terminal::Clear(ClearType::FromCursorDown)
+            )?;
+            stdout.flush()?;
+
+            let mut input = initial.to_string();
+            let custom_scope = loop {
+                input = match edit_prompt_input(&input, |_| Ok(()))? {
+                    Input::Submitted(input) => input,
+                    Input::Back => return Ok(Input::Back),
+                };
+                let trimmed = input.trim();
+                if !trimmed.is_empty() || !config.scope_required {
+                    break trimmed.to_string();
+                }
+
+                execute!(
+                    stdout,
+                    cursor::MoveToNextLine(1),
+                    SetForegroundColor(Color::Red),
+                    Print(">> [ERROR] scope is required"),
+                    ResetColor,
+                    cursor::RestorePosition,
+                    terminal::Clear(ClearType::UntilNewLine),
+                    SetForegroundColor(Color::DarkGreen),
+                )?;
+                stdout.flush()?;
+            };
 
-        execute!(stdout, Print("\n"))?;
-        format!("({})", custom_scope.trim())
-    } else {
-        "".to_string()
+            execute!(
+                stdout,
+                ResetColor,
+                Print("\n"),
+                terminal::Clear(ClearType::FromCursorDown)
+            )?;
+            custom_scope
+        }
+        Scope::EMPTY => String::new(),
+        scope => scope.to_string(),
     };
 
-    Ok(final_scope)
+    Ok(Input::Submitted(final_scope))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Type,
+    Scope,
+    Subject,
+    Body,
+    Breaking,
+    Issues,
+}
+
+pub const STEPS: [Step; 6] = [
+    Step::Type,
+    Step::Scope,
+    Step::Subject,
+    Step::Body,
+    Step::Breaking,
+    Step::Issues,
+];
+
+impl Step {
+    pub fn label(self) -> &'static str {
+        match self {
+            Step::Type => "type",
+            Step::Scope => "scope",
+            Step::Subject => "subject",
+            Step::Body => "body",
+            Step::Breaking => "breaking change",
+            Step::Issues => "issues",
+        }
+    }
+
+    fn is_answered(self, answers: &Answers) -> bool {
+        match self {
+            Step::Type => answers.commit_type.is_some(),
+            Step::Scope => answers.scope.is_some(),
+            Step::Subject => answers.subject.is_some(),
+            Step::Body => answers.body.is_some(),
+            Step::Breaking => answers.breaking.is_some(),
+            Step::Issues => answers.issues.is_some(),
+        }
+    }
+
+    /// The question and answer lines this step leaves on screen once answered.
+    fn summary<'a>(self, config: &'a Config, answers: &Answers) -> Vec<(&'a str, String)> {
+        let messages = &config.messages;
+        let value = |answer: &Option<String>| answer.clone().unwrap_or_default();
+
+        match self {
+            Step::Type => vec![(&messages.commit_type, value(&answers.commit_type))],
+            Step::Scope => vec![(&messages.scope, value(&answers.scope))],
+            Step::Subject => vec![(&messages.subject, value(&answers.subject))],
+            Step::Body => vec![(&messages.body, value(&answers.body).replace('\n', " | "))],
+            Step::Breaking if answers.breaking == Some(true) => vec![
+                (&messages.breaking_confirm, "Yes".to_string()),
+                (
+                    &messages.breaking,
+                    value(&answers.breaking_note).replace('\n', " | "),
+                ),
+            ],
+            Step::Breaking => vec![(&messages.breaking_confirm, "No".to_string())],
+            Step::Issues => vec![(&messages.footer_prefix, value(&answers.issues))],
+        }
+    }
+}
+
+/// Prompts for every answer that is still `None`, starting each prompt from the
+/// matching value in `defaults`. `checkpoint` runs after every answer.
+///
+/// The prompts form a wizard: Shift+Tab or Esc returns to the previous step, which
+/// starts from the answer it was given before.
+pub fn read_missing_answers(
+    config: &Config,
+    suggested_scopes: &[String],
+    answers: &mut Answers,
+    defaults: &Answers,
+    mut checkpoint: impl FnMut(&Answers) -> io::Result<()>,
+) -> io::Result<()> {
+    let steps: Vec<Step> = STEPS
+        .into_iter()
+        .filter(|step| !step.is_answered(answers))
+        .collect();
+    let mut index = 0;
+    let mut redraw = false;
+
+    while let Some(&step) = steps.get(index) {
+        if redraw {
+            render_answered_steps(config, answers, &steps[..index])?;
+        }
+
+        match read_step(step, config, suggested_scopes, answers, defaults)? {
+            Input::Submitted(()) => {
+                checkpoint(answers)?;
+                index += 1;
+                redraw = false;
+            }
+            Input::Back => {
+                index = index.saturating_sub(1);
+                redraw = true;
+            }
+        }
+    }
+
+    if answers.footers.is_empty() {
+        answers.footers = defaults.footers.clone();
+    }
+
+    answers.fill_defaults();
+    Ok(())
+}
+
+/// Asks the prompt of a single step and stores the answer. Returns `Input::Back`
+/// without touching `answers` when the user leaves the step.
+pub fn read_step(
+    step: Step,
+    config: &Config,
+    suggested_scopes: &[String],
+    answers: &mut Answers,
+    defaults: &Answers,
+) -> io::Result<Input<()>> {
+    let initial = |answer: &Option<String>, default: &Option<String>| {
+        answer.clone().or(default.clone()).unwrap_or_default()
+    };
+
+    let input = match step {
+        Step::Type => {
+            let initial = initial(&answers.commit_type, &defaults.commit_type);
+            read_commit_type(config, &initial)?.map(|t| answers.commit_type = Some(t.key))
+        }
+        Step::Scope => {
+            let initial = answers.scope.clone().or(defaults.scope.clone());
+            loop {
+                let chosen_scope = match render_scope(config, suggested_scopes, initial.as_deref())?
+                {
+                    Input::Submitted(scope) => scope,
+                    Input::Back => break Input::Back,
+                };
+                if let Input::Submitted(scope) = read_scope(
+                    config,
+                    &chosen_scope,
+                    initial.as_deref().unwrap_or_default(),
+                )? {
+                    answers.scope = Some(scope);
+                    break Input::Submitted(());
+                }
+            }
+        }
+        Step::Subject => {
+            let initial = initial(&answers.subject, &defaults.subject);
+            // The breaking question comes after the subject, so keep room for its `!`.
+            let mut reserved = answers.clone();
+            if reserved.breaking.is_none() && config.breaking_change_style.uses_header() {
+                reserved.breaking = Some(true);
+            }
+            read_desc(config, &reserved.header_prefix(config), &initial)?
+                .map(|subject| answers.subject = Some(subject))
+        }
+        Step::Body => {
+            let initial = initial(&answers.body, &defaults.body);
+            read_multiline(
+                &config.messages.body,
+                &initial,
+                config.rules.body_max_line_length,
+            )?
+            .map(|body| answers.body = Some(body))
+        }
+        Step::Breaking => {
+            let breaking = answers.breaking.or(defaults.breaking).unwrap_or_default();
+            let note = initial(&answers.breaking_note, &defaults.breaking_note);
+            read_breaking(config, breaking, &note)?.map(|(breaking, note)| {
+                answers.breaking = Some(breaking);
+                answers.breaking_note = Some(note);
+            })
+        }
+        Step::Issues => {
+            let initial = initial(&answers.issues, &defaults.issues);
+            read_issues(config, &initial)?.map(|(prefix, refs)| {
+                answers.issues = Some(format!("{} {}", prefix.trim(), refs).trim().to_string());
+            })
+        }
+    };
+
+    Ok(input)
+}
+
+/// Redraws the answers of `steps` after going back, in the rows the prompts left them:
+/// the type on row 0 and the scope on row 1.
+fn render_answered_steps(config: &Config, answers: &Answers, steps: &[Step]) -> io::Result<()> {
+    let mut stdout = stdout();
+    execute!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+
+    for &step in steps {
+        if step == Step::Scope {
+            execute!(stdout, cursor::MoveTo(0, 1))?;
+        }
+        for (question, answer) in step.summary(config, answers) {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::DarkGreen),
+                Print("? "),
+                ResetColor,
+                Print(question),
+                SetForegroundColor(Color::DarkGreen),
+                Print(format!(" {}", answer)),
+                ResetColor,
+                cursor::MoveToNextLine(1),
+            )?;
+        }
+    }
+
+    stdout.flush()
 }
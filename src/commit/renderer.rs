@@ -8,8 +8,9 @@ use crossterm::{
 };
 
 use crate::commit::{
-    navigation::handle_input,
-    types::{CommitType, RenderCommit},
+    config::Config,
+    navigation::{handle_input, select_index, Input},
+    types::{CommitType, RenderCommit, Scope},
 };
 
 pub fn render_commit(stdout: &mut impl Write, parts: &RenderCommit) -> io::Result<()> {
@@ -37,6 +38,8 @@ pub fn render_commit(stdout: &mut impl Write, parts: &RenderCommit) -> io::Resul
         Print(inner),
         ResetColor,
         Print(close_paren),
+        SetForegroundColor(Color::Red),
+        Print(if parts.breaking_marker { "!" } else { "" }),
         ResetColor,
         Print(format!(": {}", &parts.desc))
     )?;
@@ -59,6 +62,14 @@ pub fn render_commit(stdout: &mut impl Write, parts: &RenderCommit) -> io::Resul
             ResetColor
         )?;
     }
+    for (index, footer) in parts.extra_footers.iter().enumerate() {
+        let separator = if index == 0 && parts.issue_refs.is_empty() {
+            "\n\n"
+        } else {
+            "\n"
+        };
+        execute!(stdout, Print(format!("{}{}", separator, footer)))?;
+    }
 
     execute!(
         stdout,
@@ -137,14 +148,38 @@ pub fn render_options(
     stdout.flush()
 }
 
-pub fn render_scope() -> io::Result<String> {
+pub fn render_scope(
+    config: &Config,
+    suggested: &[String],
+    initial: Option<&str>,
+) -> io::Result<Input<String>> {
     let mut stdout = stdout();
-    let scopes = ["empty", "custom"];
+    let scopes = config.scope_options(suggested);
     let mut selected = 0;
     let mut offset = 0;
     let mut cursor = 0;
-    let window_size = 2;
+    let window_size = scopes.len().min(7);
+
+    // Without an earlier answer the cursor stays on the first option, the best
+    // suggestion. Only an answered "no scope" moves it to `empty`.
+    let initial = match initial {
+        None => None,
+        Some("") => Some(Scope::EMPTY),
+        Some(scope) if scopes.iter().any(|s| s.name == scope) => Some(scope),
+        Some(_) => Some(Scope::CUSTOM),
+    };
+    if let Some(index) = initial.and_then(|initial| scopes.iter().position(|s| s.name == initial)) {
+        select_index(
+            &mut selected,
+            &mut cursor,
+            &mut offset,
+            scopes.len(),
+            window_size,
+            index,
+        );
+    }
 
+    terminal::enable_raw_mode()?;
     stdout.flush()?;
 
     let chosen_scope = loop {
@@ -154,44 +189,62 @@ pub fn render_scope() -> io::Result<String> {
             cursor::MoveTo(0, 1),
             Print("? "),
             ResetColor,
-            Print("Denote the SCOPE of this change (optional):"),
+            Print(&config.messages.scope),
             ResetColor,
         )?;
 
+        if suggested.len() > 1 {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Yellow),
+                Print(format!(
+                    " ⚠ staged changes span several scopes: {}",
+                    suggested.join(", ")
+                )),
+                ResetColor,
+            )?;
+        }
+
         render_options(
             &mut stdout,
             &scopes
                 .iter()
-                .map(|s| CommitType::new(s, ""))
+                .map(|s| CommitType::new(&s.name, &s.description))
                 .collect::<Vec<_>>(),
             selected,
             offset,
             window_size,
         )?;
 
-        if let Some(chosen) = handle_input(
+        let chosen = match handle_input(
             &mut selected,
             &mut cursor,
             &mut offset,
             scopes.len(),
             window_size,
         )? {
-            terminal::disable_raw_mode()?;
-            execute!(
-                stdout,
-                terminal::Clear(ClearType::CurrentLine),
-                cursor::RestorePosition,
-                cursor::MoveTo(0, 1),
-                Print("? "),
-                ResetColor,
-                Print("Denote the SCOPE of this change (optional):"),
-                SetForegroundColor(Color::DarkGreen),
-                Print(format!(" {}\n", scopes[chosen])),
-                ResetColor,
-            )?;
-            break scopes[chosen].to_string();
-        }
+            Some(Input::Submitted(chosen)) => chosen,
+            Some(Input::Back) => return Ok(Input::Back),
+            None => continue,
+        };
+
+        terminal::disable_raw_mode()?;
+        execute!(
+            stdout,
+            terminal::Clear(ClearType::CurrentLine),
+            cursor::RestorePosition,
+            cursor::MoveTo(0, 1),
+            terminal::Clear(ClearType::UntilNewLine),
+            Print("? "),
+            ResetColor,
+            Print(&config.messages.scope),
+            SetForegroundColor(Color::DarkGreen),
+            Print(format!(" {}\n", scopes[chosen].name)),
+            ResetColor,
+            terminal::Clear(ClearType::FromCursorDown),
+        )?;
+        break scopes[chosen].name.clone();
     };
 
-    Ok(chosen_scope)
+    Ok(Input::Submitted(chosen_scope))
 }
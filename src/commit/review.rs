@@ -0,0 +1,245 @@
+use std::{
+    fs,
+    io::{self, stdout, Write},
+    process::{Command, Stdio},
+};
+
+use crossterm::{
+    cursor, execute,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::commit::{
+    answers::Answers,
+    config::Config,
+    editor::edit_text,
+    git::git_path,
+    message::ConventionalCommit,
+    navigation::{handle_input, Input},
+    reader::{read_step, Step, STEPS},
+    renderer::{render_commit, render_options},
+    types::CommitType,
+};
+
+pub const CLIPBOARD_FILE: &str = "COMMITZ_CLIPBOARD";
+const REVIEW_FILE: &str = "COMMITZ_REVIEW";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Commit,
+    Edit(Step),
+    OpenEditor,
+    Copy,
+    Cancel,
+}
+
+impl Action {
+    fn option(self, answers: &Answers) -> CommitType {
+        let value = |answer: &Option<String>| answer.clone().unwrap_or_default();
+        match self {
+            Action::Commit => CommitType::new("commit", "run git commit with this message"),
+            Action::Edit(step) => {
+                let current = match step {
+                    Step::Type => value(&answers.commit_type),
+                    Step::Scope => value(&answers.scope),
+                    Step::Subject => value(&answers.subject),
+                    Step::Body => value(&answers.body).replace('\n', " | "),
+                    Step::Breaking if answers.breaking == Some(true) => {
+                        format!("yes {}", value(&answers.breaking_note)).replace('\n', " | ")
+                    }
+                    Step::Breaking => "no".to_string(),
+                    Step::Issues => value(&answers.issues),
+                };
+                CommitType::new(&format!("edit {}", step.label()), &current)
+            }
+            Action::OpenEditor => CommitType::new("open in $EDITOR", "edit the whole message"),
+            Action::Copy => CommitType::new("copy", "copy the message to the clipboard"),
+            Action::Cancel => CommitType::new("cancel", "quit without committing"),
+        }
+    }
+}
+
+/// Shows the preview with a menu to re-edit any section before committing.
+/// `checkpoint` runs after every edit. Returns `false` when the user cancels.
+pub fn review(
+    config: &Config,
+    suggested_scopes: &[String],
+    answers: &mut Answers,
+    mut checkpoint: impl FnMut(&Answers) -> io::Result<()>,
+) -> io::Result<bool> {
+    let actions: Vec<Action> = [Action::Commit]
+        .into_iter()
+        .chain(STEPS.into_iter().map(Action::Edit))
+        .chain([Action::OpenEditor, Action::Copy, Action::Cancel])
+        .collect();
+    let mut stdout = stdout();
+    let mut status: Option<(Color, String)> = None;
+
+    loop {
+        let errors = answers.validate(config);
+        if let (None, Some(error)) = (&status, errors.first()) {
+            status = Some((Color::Red, error.clone()));
+        }
+
+        let action = match read_action(config, answers, &actions, status.take())? {
+            Action::Commit if !errors.is_empty() => {
+                status = Some((Color::Red, errors[0].clone()));
+                continue;
+            }
+            action => action,
+        };
+
+        match action {
+            Action::Commit => {
+                execute!(
+                    stdout,
+                    terminal::Clear(ClearType::All),
+                    cursor::MoveTo(0, 0)
+                )?;
+                terminal::disable_raw_mode()?;
+                render_commit(&mut stdout, &answers.render(config))?;
+                execute!(stdout, Print("Commit confirmed!\n"))?;
+                return Ok(true);
+            }
+            Action::Cancel => {
+                execute!(
+                    stdout,
+                    LeaveAlternateScreen,
+                    SetForegroundColor(Color::Red),
+                    Print("❌ Commit canceled by user.\n"),
+                    ResetColor
+                )?;
+                terminal::disable_raw_mode()?;
+                return Ok(false);
+            }
+            Action::Edit(step) => {
+                execute!(
+                    stdout,
+                    terminal::Clear(ClearType::All),
+                    cursor::MoveTo(0, 0)
+                )?;
+                let defaults = answers.clone();
+                if read_step(step, config, suggested_scopes, answers, &defaults)?
+                    == Input::Submitted(())
+                {
+                    checkpoint(answers)?;
+                }
+            }
+            Action::OpenEditor => {
+                let message = ConventionalCommit::from(&answers.render(config)).to_string();
+                terminal::disable_raw_mode()?;
+                execute!(stdout, LeaveAlternateScreen)?;
+                let edited = edit_text(REVIEW_FILE, &format!("{}\n", message));
+                execute!(stdout, EnterAlternateScreen)?;
+
+                status = Some(match edited {
+                    Ok(edited) if edited.is_empty() => {
+                        (Color::Yellow, "empty message, nothing changed".to_string())
+                    }
+                    Ok(edited) => {
+                        *answers = Answers::from_message(&edited);
+                        answers.fill_defaults();
+                        checkpoint(answers)?;
+                        (
+                            Color::DarkGreen,
+                            "message updated from the editor".to_string(),
+                        )
+                    }
+                    Err(e) => (Color::Red, e.to_string()),
+                });
+            }
+            Action::Copy => {
+                let message = ConventionalCommit::from(&answers.render(config)).to_string();
+                status = Some(match copy_to_clipboard(&message) {
+                    Ok(target) => (Color::DarkGreen, format!("copied to {}", target)),
+                    Err(e) => (Color::Red, e.to_string()),
+                });
+            }
+        }
+    }
+}
+
+fn read_action(
+    config: &Config,
+    answers: &Answers,
+    actions: &[Action],
+    status: Option<(Color, String)>,
+) -> io::Result<Action> {
+    let mut stdout = stdout();
+    let options: Vec<CommitType> = actions.iter().map(|a| a.option(answers)).collect();
+    let window_size = options.len();
+    let mut selected = 0;
+    let mut offset = 0;
+    let mut cursor = 0;
+
+    terminal::enable_raw_mode()?;
+    loop {
+        execute!(
+            stdout,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::DarkGreen),
+            Print("? "),
+            ResetColor,
+            Print(&config.messages.confirm),
+        )?;
+        if let Some((color, text)) = &status {
+            execute!(
+                stdout,
+                SetForegroundColor(*color),
+                Print(format!(" {}", text)),
+                ResetColor
+            )?;
+        }
+
+        render_options(&mut stdout, &options, selected, offset, window_size)?;
+        execute!(stdout, cursor::MoveTo(0, window_size as u16 + 4))?;
+        render_commit(&mut stdout, &answers.render(config))?;
+        stdout.flush()?;
+
+        if let Some(Input::Submitted(chosen)) = handle_input(
+            &mut selected,
+            &mut cursor,
+            &mut offset,
+            options.len(),
+            window_size,
+        )? {
+            return Ok(actions[chosen]);
+        }
+    }
+}
+
+/// Copies the message with the first clipboard tool that works, falling back to a
+/// file in the git directory. Returns where the message went.
+fn copy_to_clipboard(message: &str) -> io::Result<String> {
+    const TOOLS: [(&str, &[&str]); 5] = [
+        ("pbcopy", &[]),
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+        ("clip", &[]),
+    ];
+
+    for (tool, args) in TOOLS {
+        let Ok(mut child) = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(message.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok("the clipboard".to_string());
+        }
+    }
+
+    let path = git_path(CLIPBOARD_FILE)?;
+    fs::write(&path, format!("{}\n", message))?;
+    Ok(path.display().to_string())
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub header_max_length: usize,
+    pub subject_min_length: usize,
+    pub subject_case: SubjectCase,
+    pub allow_subject_period: bool,
+    pub body_max_line_length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubjectCase {
+    Any,
+    Lower,
+    Upper,
+    Sentence,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            header_max_length: 100,
+            subject_min_length: 1,
+            subject_case: SubjectCase::Any,
+            allow_subject_period: false,
+            body_max_line_length: 72,
+        }
+    }
+}
+
+impl Rules {
+    pub fn remaining_header_chars(&self, header: &str) -> Option<isize> {
+        (self.header_max_length > 0)
+            .then(|| self.header_max_length as isize - header.chars().count() as isize)
+    }
+
+    pub fn check_header_length(&self, header: &str) -> Option<String> {
+        match self.remaining_header_chars(header) {
+            Some(remaining) if remaining < 0 => Some(format!(
+                "header must not be longer than {} characters",
+                self.header_max_length
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn check_subject(&self, subject: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        let subject = subject.trim();
+
+        if subject.chars().count() < self.subject_min_length.max(1) {
+            errors.push(if self.subject_min_length > 1 {
+                format!(
+                    "subject must be at least {} characters",
+                    self.subject_min_length
+                )
+            } else {
+                "input is required".to_string()
+            });
+            return errors;
+        }
+
+        let first = subject.chars().next().unwrap_or_default();
+        let case_error = match self.subject_case {
+            SubjectCase::Any => None,
+            SubjectCase::Lower if first.is_uppercase() => {
+                Some("subject must start with a lowercase letter")
+            }
+            SubjectCase::Sentence if first.is_lowercase() => {
+                Some("subject must start with an uppercase letter")
+            }
+            SubjectCase::Upper if subject.chars().any(char::is_lowercase) => {
+                Some("subject must be uppercase")
+            }
+            _ => None,
+        };
+        errors.extend(case_error.map(str::to_string));
+
+        if !self.allow_subject_period && subject.ends_with('.') {
+            errors.push("subject must not end with a period".to_string());
+        }
+
+        errors
+    }
+
+    pub fn check_body_line(&self, line: &str) -> Option<String> {
+        (self.body_max_line_length > 0 && line.chars().count() > self.body_max_line_length).then(
+            || {
+                format!(
+                    "body lines must not be longer than {} characters",
+                    self.body_max_line_length
+                )
+            },
+        )
+    }
+
+    pub fn wrap_body(&self, body: &str) -> String {
+        if self.body_max_line_length == 0 {
+            return body.to_string();
+        }
+
+        let mut in_fence = false;
+        body.lines()
+            .map(|line| {
+                if keeps_layout(line, &mut in_fence) {
+                    line.to_string()
+                } else {
+                    wrap_line(line, self.body_max_line_length)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Wraps the value of a footer such as `BREAKING CHANGE` so the whole footer fits
+    /// the body width. Every line after the first is indented, the way git folds
+    /// trailers, so it is not read as a footer of its own.
+    pub fn wrap_footer(&self, token: &str, value: &str) -> String {
+        if self.body_max_line_length == 0 {
+            return value.to_string();
+        }
+
+        let prefix = format!("{}: ", token);
+        let footer: Vec<String> = value
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let first = if i == 0 { prefix.as_str() } else { "  " };
+                wrap_words(first, line, self.body_max_line_length, "  ")
+            })
+            .collect();
+        let footer = footer.join("\n");
+        footer.strip_prefix(&prefix).unwrap_or_default().to_string()
+    }
+}
+
+/// Whether a body line keeps its layout instead of being wrapped or length-checked:
+/// code fences and the code between them, indented lines and list items. Rewrapping
+/// those would break code and lose the hanging indent of list items. `in_fence`
+/// carries the fence state from one line to the next.
+pub fn keeps_layout(line: &str, in_fence: &mut bool) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        *in_fence = !*in_fence;
+        return true;
+    }
+    *in_fence || trimmed.len() < line.len() || is_list_item(trimmed)
+}
+
+fn is_list_item(line: &str) -> bool {
+    let numbered = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    if numbered.len() < line.len() {
+        numbered.starts_with(". ") || numbered.starts_with(") ")
+    } else {
+        line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ")
+    }
+}
+
+fn wrap_line(line: &str, width: usize) -> String {
+    if line.chars().count() <= width {
+        return line.to_string();
+    }
+    wrap_words("", line, width, "")
+}
+
+/// Fills lines of at most `width` characters with the words of `text`, after `prefix`
+/// on the first line and `indent` on the others. A word longer than `width` gets a
+/// line to itself.
+fn wrap_words(prefix: &str, text: &str, width: usize, indent: &str) -> String {
+    let mut lines = Vec::new();
+    let mut current = prefix.to_string();
+    let mut has_words = false;
+    for word in text.split_whitespace() {
+        if has_words && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::replace(&mut current, indent.to_string()));
+            has_words = false;
+        }
+        if has_words {
+            current.push(' ');
+        }
+        current.push_str(word);
+        has_words = true;
+    }
+    lines.push(current);
+
+    lines.join("\n")
+}
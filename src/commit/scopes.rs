@@ -0,0 +1,42 @@
+use std::cmp::Reverse;
+
+use glob::{MatchOptions, Pattern};
+
+use crate::commit::config::Config;
+
+pub fn infer_scopes(config: &Config, files: &[String]) -> Vec<String> {
+    if !config.infer_scopes {
+        return Vec::new();
+    }
+
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for scope in files.iter().filter_map(|f| scope_for_path(config, f)) {
+        match counts.iter_mut().find(|(name, _)| *name == scope) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((scope, 1)),
+        }
+    }
+
+    counts.retain(|(name, _)| config.scope_allowed(name));
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts.into_iter().map(|(name, _)| name).collect()
+}
+
+pub fn scope_for_path(config: &Config, path: &str) -> Option<String> {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+
+    let matched = config.scope_rules.iter().find(|rule| {
+        Pattern::new(&rule.pattern)
+            .map(|p| p.matches_with(path, options))
+            .unwrap_or(false)
+    });
+
+    if let Some(rule) = matched {
+        return Some(rule.scope.clone());
+    }
+
+    path.split_once('/').map(|(dir, _)| dir.to_string())
+}
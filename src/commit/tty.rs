@@ -0,0 +1,52 @@
+use std::io;
+
+/// Points stdout at the controlling terminal until `restore` is called, so prompts
+/// stay on screen while the real stdout is piped or redirected to a file.
+pub struct TerminalStdout {
+    #[cfg(unix)]
+    saved: std::os::fd::OwnedFd,
+}
+
+#[cfg(unix)]
+impl TerminalStdout {
+    /// Returns `None` when there is no controlling terminal.
+    pub fn attach() -> io::Result<Option<TerminalStdout>> {
+        use std::os::fd::{AsFd, AsRawFd};
+
+        let Ok(tty) = std::fs::OpenOptions::new().write(true).open("/dev/tty") else {
+            return Ok(None);
+        };
+        let saved = io::stdout().as_fd().try_clone_to_owned()?;
+        // SAFETY: `tty` stays open for the call, so its fd is valid. dup2 only replaces
+        // fd 1 with a copy of it; the original stdout lives on in `saved`.
+        if unsafe { libc::dup2(tty.as_raw_fd(), 1) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Some(TerminalStdout { saved }))
+    }
+
+    /// Puts the original stdout back on fd 1.
+    pub fn restore(self) -> io::Result<()> {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        io::stdout().flush()?;
+        // SAFETY: `saved` is owned by `self` and open until it drops after the call.
+        if unsafe { libc::dup2(self.saved.as_raw_fd(), 1) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(unix))]
+impl TerminalStdout {
+    pub fn attach() -> io::Result<Option<TerminalStdout>> {
+        Ok(None)
+    }
+
+    pub fn restore(self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,25 +1,73 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitType {
     pub key: String,
     pub description: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "ScopeEntry")]
+pub struct Scope {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ScopeEntry {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        description: String,
+    },
+}
+
+impl From<ScopeEntry> for Scope {
+    fn from(entry: ScopeEntry) -> Self {
+        match entry {
+            ScopeEntry::Name(name) => Scope::new(&name, ""),
+            ScopeEntry::Full { name, description } => Scope::new(&name, &description),
+        }
+    }
+}
+
+impl Scope {
+    pub const EMPTY: &'static str = "empty";
+    pub const CUSTOM: &'static str = "custom";
+
+    pub fn new(name: &str, description: &str) -> Self {
+        Scope {
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopeRule {
+    pub pattern: String,
+    pub scope: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct CommitTypes {
     pub types: Vec<CommitType>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderCommit {
     pub chosen_type: String,
     pub final_scope: String,
     pub desc: String,
     pub longer_description: String,
+    pub breaking_marker: bool,
     pub breaking_changes: String,
     pub issue_prefix: String,
     pub issue_refs: String,
+    #[serde(default)]
+    pub extra_footers: Vec<String>,
 }
 
 impl RenderCommit {
@@ -37,11 +85,23 @@ impl RenderCommit {
             final_scope,
             desc,
             longer_description,
+            breaking_marker: false,
             breaking_changes,
             issue_prefix,
             issue_refs,
+            extra_footers: Vec::new(),
         }
     }
+
+    pub fn with_breaking_marker(mut self, breaking_marker: bool) -> Self {
+        self.breaking_marker = breaking_marker;
+        self
+    }
+
+    pub fn with_extra_footers(mut self, extra_footers: Vec<String>) -> Self {
+        self.extra_footers = extra_footers;
+        self
+    }
 }
 
 const COMMIT_TYPES_JSON: &str = r#"
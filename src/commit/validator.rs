@@ -114,19 +114,9 @@ pub fn ensure_git_repo() -> io::Result<()> {
     Ok(())
 }
 
-pub fn ensure_staged_files() -> io::Result<()> {
-    let status = Command::new("git")
-        .args([
-            "diff",
-            "--cached",
-            "--no-ext-diff",
-            "--name-only",
-            "--quiet",
-        ])
-        .status();
-
-    match status {
-        Ok(s) if !s.success() => Ok(()),
+pub fn ensure_staged_files() -> io::Result<Vec<String>> {
+    match staged_files() {
+        Ok(files) if !files.is_empty() => Ok(files),
         Ok(_) => {
             execute!(
                 stdout(),
@@ -147,3 +137,20 @@ pub fn ensure_staged_files() -> io::Result<()> {
         }
     }
 }
+
+pub fn staged_files() -> io::Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--no-ext-diff", "--name-only", "-z"])
+        .stderr(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("git diff --cached failed"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| path.to_string())
+        .collect())
+}
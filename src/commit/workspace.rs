@@ -0,0 +1,326 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::{MatchOptions, Pattern};
+use serde_json::Value;
+
+use crate::commit::{
+    config::{repo_root, Config},
+    types::{Scope, ScopeRule},
+};
+
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    pub name: String,
+    pub path: String,
+}
+
+pub fn add_workspace_scopes(config: &mut Config) {
+    if !config.workspace_scopes {
+        return;
+    }
+    let Some(root) = repo_root() else {
+        return;
+    };
+
+    for member in discover_members(&root) {
+        if !config.scopes.iter().any(|s| s.name == member.name) {
+            config.scopes.push(Scope::new(
+                &member.name,
+                &format!("workspace member ({})", member.path),
+            ));
+        }
+        config.scope_rules.push(ScopeRule {
+            pattern: format!("{}/**", Pattern::escape(&member.path)),
+            scope: member.name,
+        });
+    }
+}
+
+pub fn discover_members(root: &Path) -> Vec<WorkspaceMember> {
+    let mut members: Vec<WorkspaceMember> = Vec::new();
+
+    let mut found = cargo_members(root);
+    found.extend(npm_members(root));
+    found.extend(pnpm_members(root));
+
+    for member in found {
+        if !members.iter().any(|m| m.path == member.path) {
+            members.push(member);
+        }
+    }
+
+    members
+}
+
+fn cargo_members(root: &Path) -> Vec<WorkspaceMember> {
+    let Some(manifest) = read_toml(&root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Some(workspace) = manifest.get("workspace") else {
+        return Vec::new();
+    };
+
+    let patterns = toml_strings(workspace.get("members"));
+    let excluded = toml_strings(workspace.get("exclude"));
+
+    expand(root, &patterns, &excluded)
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = read_toml(&dir.join("Cargo.toml"))?;
+            let name = manifest.get("package")?.get("name")?.as_str()?;
+            member(root, &dir, name)
+        })
+        .collect()
+}
+
+fn npm_members(root: &Path) -> Vec<WorkspaceMember> {
+    let Some(manifest) = read_json(&root.join("package.json")) else {
+        return Vec::new();
+    };
+
+    let workspaces = match manifest.get("workspaces") {
+        Some(Value::Object(map)) => map.get("packages"),
+        other => other,
+    };
+    let patterns: Vec<String> = match workspaces {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => return Vec::new(),
+    };
+
+    package_json_members(root, &patterns)
+}
+
+fn pnpm_members(root: &Path) -> Vec<WorkspaceMember> {
+    let Ok(content) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+
+    package_json_members(root, &pnpm_packages(&content))
+}
+
+fn package_json_members(root: &Path, patterns: &[String]) -> Vec<WorkspaceMember> {
+    let (excluded, included): (Vec<String>, Vec<String>) =
+        patterns.iter().cloned().partition(|p| p.starts_with('!'));
+    let excluded: Vec<String> = excluded
+        .iter()
+        .map(|p| p.trim_start_matches('!').to_string())
+        .collect();
+
+    expand(root, &included, &excluded)
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = read_json(&dir.join("package.json"))?;
+            let name = manifest.get("name")?.as_str()?.to_string();
+            member(root, &dir, &name)
+        })
+        .collect()
+}
+
+fn pnpm_packages(content: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("packages:") {
+            let rest = rest.trim();
+            if let Some(inline) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+                packages.extend(inline.split(',').map(unquote).filter(|p| !p.is_empty()));
+                return packages;
+            }
+            in_packages = true;
+            continue;
+        }
+
+        if !line.starts_with(' ') && !line.starts_with('-') {
+            in_packages = false;
+        } else if in_packages {
+            if let Some(item) = line.trim_start().strip_prefix('-') {
+                packages.push(unquote(item));
+            }
+        }
+    }
+
+    packages
+}
+
+/// Directories under `root` that match one of `patterns` and none of `excluded`, in
+/// pattern order. Like npm and pnpm, the walk skips `node_modules` and hidden
+/// directories, and it only goes as deep as the patterns can reach.
+fn expand(root: &Path, patterns: &[String], excluded: &[String]) -> Vec<PathBuf> {
+    let patterns: Vec<&str> = patterns
+        .iter()
+        .map(|p| p.trim_start_matches("./").trim_end_matches('/'))
+        .collect();
+    let excluded: Vec<Pattern> = excluded
+        .iter()
+        .filter_map(|p| Pattern::new(p.trim_start_matches("./").trim_end_matches('/')).ok())
+        .collect();
+    let depth = patterns
+        .iter()
+        .map(|p| {
+            if p.contains("**") {
+                usize::MAX
+            } else {
+                p.split('/').count()
+            }
+        })
+        .max()
+        .unwrap_or(0);
+
+    let mut candidates = Vec::new();
+    walk(root, depth, &mut candidates);
+
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    let mut dirs = Vec::new();
+    for pattern in patterns.iter().filter_map(|p| Pattern::new(p).ok()) {
+        for dir in &candidates {
+            let relative = relative_path(root, dir);
+            if pattern.matches_with(&relative, options)
+                && !excluded.iter().any(|p| p.matches(&relative))
+                && !dirs.contains(dir)
+            {
+                dirs.push(dir.clone());
+            }
+        }
+    }
+
+    dirs
+}
+
+fn walk(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut children: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && name != "node_modules"
+        })
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+
+    for child in children {
+        found.push(child.clone());
+        walk(&child, depth - 1, found);
+    }
+}
+
+fn member(root: &Path, dir: &Path, name: &str) -> Option<WorkspaceMember> {
+    let path = relative_path(root, dir);
+    if path.is_empty() || name.is_empty() {
+        return None;
+    }
+
+    Some(WorkspaceMember {
+        name: name.to_string(),
+        path,
+    })
+}
+
+fn relative_path(root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(root)
+        .unwrap_or(dir)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"')
+        .to_string()
+}
+
+fn toml_strings(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pnpm_block_list() {
+        let content = "packages:\n  - 'apps/*'\n  - \"packages/**\" # all of them\n  # - old/*\n  - '!**/test/**'\ncatalog:\n  - react\n";
+        assert_eq!(
+            pnpm_packages(content),
+            vec!["apps/*", "packages/**", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn pnpm_inline_list() {
+        assert_eq!(
+            pnpm_packages("# workspace\npackages: ['apps/*', \"libs/*\"]\n"),
+            vec!["apps/*", "libs/*"]
+        );
+    }
+
+    #[test]
+    fn pnpm_without_packages() {
+        assert!(pnpm_packages("onlyBuiltDependencies:\n  - esbuild\n").is_empty());
+    }
+
+    #[test]
+    fn expand_skips_node_modules_and_hidden_dirs() {
+        let root = std::env::temp_dir().join(format!("commitz-workspace-{}", std::process::id()));
+        for dir in [
+            "apps/web/node_modules/react",
+            "apps/.cache/tool",
+            "apps/api/src",
+            "apps/legacy",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        let dirs = expand(
+            &root,
+            &["apps/**".to_string()],
+            &["apps/legacy".to_string()],
+        );
+        let relative: Vec<String> = dirs.iter().map(|d| relative_path(&root, d)).collect();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(relative, vec!["apps/api", "apps/api/src", "apps/web"]);
+    }
+}
@@ -1,7 +1,21 @@
 pub mod commit {
+    pub mod answers;
+    pub mod config;
+    pub mod draft;
+    pub mod editor;
+    pub mod git;
+    pub mod hooks;
+    pub mod line_editor;
+    pub mod lint;
+    pub mod message;
     pub mod navigation;
     pub mod reader;
     pub mod renderer;
+    pub mod review;
+    pub mod rules;
+    pub mod scopes;
+    pub mod tty;
     pub mod types;
     pub mod validator;
+    pub mod workspace;
 }
@@ -1,82 +1,224 @@
+use clap::{error::ErrorKind, CommandFactory};
+use clap_complete::generate;
+use cli::{
+    ChangelogArgs, Cli, Command, CommitArgs, ConfigCommand, HookArgs, HookCommand, HookSelection,
+    InitArgs, LintArgs, EXIT_FAILURE,
+};
 use commitz::commit::{
-    reader::{read_commit_type, read_desc, read_issues, read_multiline, read_scope},
-    renderer::{render_commit, render_scope},
+    answers::Answers,
+    config::Config,
+    draft::{
+        discard_answers, discard_render, load_answers, load_render, save_answers, save_render,
+    },
+    git::{git_path, head_message, MESSAGE_FILE},
+    hooks::{
+        attach_terminal, hooks_dir, install_hooks, should_prompt, uninstall_hook, ExistingHook,
+        HookKind,
+    },
+    lint::{lint_message_file, lint_range},
+    message::ConventionalCommit,
+    navigation::Input,
+    reader::{read_missing_answers, read_yes_no},
+    renderer::render_commit,
+    review::review,
+    scopes::infer_scopes,
+    tty::TerminalStdout,
     types::RenderCommit,
-    validator::{confirm_question, ensure_git_repo, ensure_staged_files},
+    validator::{confirm_question, ensure_git_repo, ensure_staged_files, staged_files},
+    workspace::add_workspace_scopes,
 };
 use crossterm::{
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{self, EnterAlternateScreen},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{
-    env,
-    io::{self, stdout},
-    process::Command,
+    fs,
+    io::{self, stdout, IsTerminal},
+    path::{Path, PathBuf},
+    process,
 };
 
+mod cli;
+
 fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() > 1 && (args[1] == "-v" || args[1] == "--version") {
-        println!("commitz {}", env!("CARGO_PKG_VERSION"));
-        return Ok(());
+    let cli = Cli::parse_args();
+    let overrides = &cli.overrides;
+
+    match cli.command {
+        None if cli.commit.retry => run_retry(cli.commit, overrides),
+        None => run_commit(cli.commit, overrides),
+        Some(Command::Commit(args)) if args.retry => run_retry(args, overrides),
+        Some(Command::Commit(args)) => run_commit(args, overrides),
+        Some(Command::Lint(args)) => run_lint(args, overrides),
+        Some(Command::Init(args)) => run_init(args),
+        Some(Command::Hook {
+            command: Some(command),
+            ..
+        }) => manage_hooks(command),
+        Some(Command::Hook {
+            command: None,
+            args,
+        }) => run_hook(args, overrides),
+        Some(Command::Changelog(args)) => run_changelog(args, overrides),
+        Some(Command::Config {
+            command: ConfigCommand::Show { resolved },
+        }) => show_config(resolved, overrides),
+        Some(Command::Completions { shell }) => {
+            generate(shell, &mut Cli::command(), "commitz", &mut stdout());
+            Ok(())
+        }
     }
+}
 
-    ensure_git_repo()?;
-    ensure_staged_files()?;
-    terminal::enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let chosen_type = read_commit_type()?;
-    let chosen_scope = render_scope()?;
-    let final_scope = read_scope(&chosen_scope)?;
-
-    let desc = read_desc()?;
-    let longer_description = read_multiline(
-        "Provide a LONGER description of the change (optional). Use \"|\" to break new line:\n",
-    )?;
+fn run_commit(args: CommitArgs, overrides: &[String]) -> io::Result<()> {
+    let yes = args.yes;
+    let amend = args.amend;
+    let dry_run = args.dry_run || args.output.is_some();
+    let output = args.output.clone();
+    let allow_empty = args.allow_empty || args.git_args.iter().any(|a| a == "--allow-empty");
+    let git_options = args.git_options();
+    let mut answers = commit_answers(args);
 
-    let mut breaking_changes = String::new();
+    if yes && !amend && (answers.commit_type.is_none() || answers.subject.is_none()) {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--yes needs at least --type and --subject",
+            )
+            .exit();
+    }
 
-    if chosen_scope == "custom" {
-        breaking_changes =
-            read_multiline("List any BREAKING CHANGES (optional). Use \"|\" to break new line:\n")?;
+    ensure_git_repo()?;
+    let defaults = if amend {
+        Answers::from_message(&head_message().unwrap_or_else(|e| fail(e)))
+    } else {
+        Answers::default()
+    };
+    if yes {
+        answers = answers.or(defaults.clone());
+        answers.fill_defaults();
     }
-    let (issue_prefix, issue_refs) = read_issues()?;
-    let mut commit_message = format!("{}{}: {}", chosen_type.key, final_scope, desc);
+    let staged_files = if dry_run || amend || allow_empty {
+        staged_files().unwrap_or_default()
+    } else {
+        ensure_staged_files()?
+    };
+
+    let mut config = load_config(overrides)?;
+    add_workspace_scopes(&mut config);
+
+    let mut stdout = stdout();
+    // The message printed by a dry-run is often piped or redirected, so the prompts
+    // are drawn on the terminal instead.
+    let terminal_stdout = if dry_run && !stdout.is_terminal() {
+        TerminalStdout::attach()?
+    } else {
+        None
+    };
+    // An amend edits HEAD, so it neither resumes nor overwrites the draft of a new commit.
+    let use_draft = !amend;
+    let interactive = compose(&config, &staged_files, &mut answers, &defaults, use_draft)?;
 
-    if !longer_description.is_empty() {
-        commit_message.push_str(&format!("\n\n{}", longer_description));
+    if dry_run {
+        let render = answers.render(&config);
+        let commit_message = ConventionalCommit::from(&render).to_string();
+        if interactive {
+            execute!(stdout, LeaveAlternateScreen)?;
+        }
+        terminal::disable_raw_mode()?;
+        if let Some(terminal_stdout) = terminal_stdout {
+            terminal_stdout.restore()?;
+        }
+        print_message(&render, &commit_message, output)?;
+        return if use_draft { discard_answers() } else { Ok(()) };
     }
-    if !breaking_changes.is_empty() {
-        commit_message.push_str(&format!("\n\nBREAKING CHANGE: {}", breaking_changes));
+
+    if yes {
+        render_commit(&mut stdout, &answers.render(&config))?;
+        execute!(stdout, ResetColor, Print("\n"))?;
+    } else {
+        execute!(stdout, EnterAlternateScreen)?;
+        let suggested_scopes = infer_scopes(&config, &staged_files);
+        if !review(
+            &config,
+            &suggested_scopes,
+            &mut answers,
+            draft_checkpoint(use_draft),
+        )? {
+            return if use_draft { discard_answers() } else { Ok(()) };
+        }
     }
-    if !issue_prefix.is_empty() {
-        commit_message.push_str(&format!("\n\n{} {}", issue_prefix, issue_refs));
+
+    let render = answers.render(&config);
+    let commit_message = ConventionalCommit::from(&render).to_string();
+    let message_file = git_path(MESSAGE_FILE).unwrap_or_else(|e| fail(e));
+    fs::write(&message_file, format!("{}\n", commit_message))?;
+    save_render(&render, &git_options)?;
+    git_commit(&message_file, &git_options)?;
+    if use_draft {
+        discard_answers()?;
     }
+    Ok(())
+}
 
-    render_commit(
-        &mut stdout,
-        &RenderCommit::new(
-            chosen_type.key,
-            final_scope,
-            desc,
-            longer_description,
-            breaking_changes,
-            issue_prefix,
-            issue_refs,
-        ),
-    )?;
+fn run_retry(args: CommitArgs, overrides: &[String]) -> io::Result<()> {
+    ensure_git_repo()?;
+    let config = load_config(overrides)?;
+    let message_file = git_path(MESSAGE_FILE).unwrap_or_else(|e| fail(e));
 
-    if confirm_question("Are you sure you want to proceed with the commit above?")? {
+    let mut stdout = stdout();
+    let mut git_options = args.git_options();
+    match load_render().unwrap_or_else(|e| fail(e)) {
+        Some(draft) => {
+            let commit_message = ConventionalCommit::from(&draft.render).to_string();
+            fs::write(&message_file, format!("{}\n", commit_message))?;
+            render_commit(&mut stdout, &draft.render)?;
+            if git_options.is_empty() {
+                git_options = draft.git_options;
+            }
+        }
+        None => {
+            let Ok(commit_message) = fs::read_to_string(&message_file) else {
+                fail(io::Error::other("there is no failed commit to retry"));
+            };
+            execute!(
+                stdout,
+                SetForegroundColor(Color::DarkGreen),
+                Print("✔ Retrying with the saved commit message:\n"),
+                ResetColor,
+                Print(format!("{}\n\n", commit_message.trim_end())),
+            )?;
+        }
+    }
+
+    if args.yes {
+        execute!(stdout, ResetColor, Print("\n"))?;
+    } else if confirm_question(&config.messages.confirm)? {
         execute!(stdout, ResetColor, Print("\nCommit confirmed!\n"))?;
     } else {
-        terminal::disable_raw_mode()?;
         return Ok(());
     }
+    git_commit(&message_file, &git_options)?;
+    // The answers draft belongs to a new commit, so an amend leaves it alone.
+    if !git_options.iter().any(|option| option == "--amend") {
+        discard_answers()?;
+    }
+    Ok(())
+}
 
-    let status = Command::new("git")
-        .args(["commit", "-m", &commit_message])
+/// Runs `git commit -F` so git applies `commit.cleanup`. The message file and the saved
+/// draft are only removed once the commit succeeds, so a failed commit can be retried
+/// with `commitz --retry`.
+fn git_commit(message_file: &Path, git_options: &[String]) -> io::Result<()> {
+    let mut stdout = stdout();
+    terminal::disable_raw_mode()?;
+
+    let status = process::Command::new("git")
+        .arg("commit")
+        .args(git_options)
+        .arg("-F")
+        .arg(message_file)
         .status()
         .expect("failed to run git commit");
 
@@ -85,17 +227,295 @@ fn main() -> io::Result<()> {
             stdout,
             SetForegroundColor(Color::Red),
             Print("❌ git commit failed\n"),
-            ResetColor
+            ResetColor,
+            Print(format!(
+                "The message was kept in {}, run `commitz --retry` to try again.\n",
+                message_file.display()
+            )),
         )?;
+        process::exit(EXIT_FAILURE);
+    }
+
+    fs::remove_file(message_file)?;
+    discard_render()?;
+    execute!(
+        stdout,
+        SetForegroundColor(Color::DarkGreen),
+        Print("\n✅ Commit successful!\n"),
+        ResetColor,
+    )
+}
+
+fn compose(
+    config: &Config,
+    staged_files: &[String],
+    answers: &mut Answers,
+    defaults: &Answers,
+    use_draft: bool,
+) -> io::Result<bool> {
+    let mut stdout = stdout();
+    let mut defaults = defaults.clone();
+    let interactive = !answers.is_complete();
+    if interactive && use_draft {
+        if let Some(draft) = load_answers().unwrap_or_default() {
+            let summary = format!(
+                "{}{}",
+                draft.header_prefix(config),
+                draft.subject.as_deref().unwrap_or("…")
+            );
+            let prompt = format!("Resume unfinished commit \"{}\"?", summary);
+            // Resumed answers only prefill the prompts, so each step can still be
+            // revisited with Shift+Tab.
+            if read_yes_no(&prompt, true)? == Input::Submitted(true) {
+                defaults = draft.or(defaults);
+            }
+        }
+    }
+    if interactive {
+        let suggested_scopes = infer_scopes(config, staged_files);
+        terminal::enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen)?;
+        read_missing_answers(
+            config,
+            &suggested_scopes,
+            answers,
+            &defaults,
+            draft_checkpoint(use_draft),
+        )?;
+    }
+
+    let errors = answers.validate(config);
+    if !errors.is_empty() {
+        terminal::disable_raw_mode()?;
+        for error in errors {
+            execute!(
+                stdout,
+                SetForegroundColor(Color::Red),
+                Print(format!("❌ {}\n", error)),
+                ResetColor
+            )?;
+        }
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    Ok(interactive)
+}
+
+/// Saves the answers given so far to the draft after each prompt, unless the commit
+/// does not use one.
+fn draft_checkpoint(use_draft: bool) -> impl FnMut(&Answers) -> io::Result<()> {
+    move |answers| {
+        if use_draft {
+            save_answers(answers)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn run_hook(args: HookArgs, overrides: &[String]) -> io::Result<()> {
+    let Some(file) = args.file else {
+        return Ok(());
+    };
+    if !should_prompt(args.source.as_deref()) || !attach_terminal()? {
+        return Ok(());
+    }
+
+    let mut config = load_config(overrides)?;
+    add_workspace_scopes(&mut config);
+    let staged_files = staged_files().unwrap_or_default();
+
+    let mut answers = Answers::default();
+    compose(
+        &config,
+        &staged_files,
+        &mut answers,
+        &Answers::default(),
+        false,
+    )?;
+    if !review(
+        &config,
+        &infer_scopes(&config, &staged_files),
+        &mut answers,
+        |_| Ok(()),
+    )? {
+        std::process::exit(EXIT_FAILURE);
+    }
+    execute!(stdout(), LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    let commit_message = ConventionalCommit::from(&answers.render(&config)).to_string();
+
+    let template = fs::read_to_string(&file).unwrap_or_default();
+    fs::write(&file, format!("{}\n{}", commit_message, template))
+}
+
+fn print_message(
+    render: &RenderCommit,
+    commit_message: &str,
+    output: Option<PathBuf>,
+) -> io::Result<()> {
+    let mut stderr = io::stderr();
+    render_commit(&mut stderr, render)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, format!("{}\n", commit_message)).unwrap_or_else(|e| fail(e));
+            execute!(
+                stderr,
+                SetForegroundColor(Color::DarkGreen),
+                Print(format!("\n✅ Wrote {}\n", path.display())),
+                ResetColor
+            )
+        }
+        None => {
+            println!("{}", commit_message);
+            Ok(())
+        }
+    }
+}
+
+fn commit_answers(args: CommitArgs) -> Answers {
+    Answers {
+        commit_type: args.commit_type,
+        scope: args.scope,
+        subject: args.subject,
+        body: args.body,
+        breaking: args.breaking.is_some().then_some(true),
+        breaking_note: args.breaking,
+        issues: args.issues,
+        footers: Vec::new(),
+    }
+}
+
+fn show_config(resolved: bool, overrides: &[String]) -> io::Result<()> {
+    let config = Config::resolve(overrides).unwrap_or_else(|e| config_error(e));
+    if resolved {
+        print!("{}", config);
     } else {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&config.config).map_err(io::Error::other)?
+        );
+    }
+
+    Ok(())
+}
+
+fn run_lint(args: LintArgs, overrides: &[String]) -> io::Result<()> {
+    let format = args.format;
+    let mut config = load_config(overrides)?;
+    add_workspace_scopes(&mut config);
+
+    let Some(file) = args.edit else {
+        let reports =
+            lint_range(&config, args.from.as_deref(), &args.to).unwrap_or_else(|e| fail(e));
+        print!("{}", format.render(&reports));
+        if reports.iter().any(|r| !r.violations.is_empty()) {
+            std::process::exit(EXIT_FAILURE);
+        }
+        return Ok(());
+    };
+
+    let message = fs::read_to_string(&file)
+        .unwrap_or_else(|e| fail(io::Error::new(e.kind(), format!("{}: {}", file, e))));
+    let violations = lint_message_file(&config, &message);
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let mut stderr = io::stderr();
+    execute!(
+        stderr,
+        SetForegroundColor(Color::Red),
+        Print("❌ Commit message does not follow Conventional Commits:\n"),
+        ResetColor
+    )?;
+    for violation in &violations {
+        execute!(stderr, Print(format!("{}:{}\n", file, violation)))?;
+    }
+    std::process::exit(EXIT_FAILURE);
+}
+
+fn run_init(_args: InitArgs) -> io::Result<()> {
+    fail(io::Error::other("`commitz init` is not implemented yet"))
+}
+
+fn manage_hooks(command: HookCommand) -> io::Result<()> {
+    ensure_git_repo()?;
+    let dir = hooks_dir().unwrap_or_else(|e| fail(e));
+
+    let changes = match command {
+        HookCommand::Install(args) => {
+            let existing = if args.force {
+                ExistingHook::Overwrite
+            } else if args.chain {
+                ExistingHook::Chain
+            } else {
+                ExistingHook::Refuse
+            };
+            install_hooks(&dir, &selected_hooks(&args.hooks), existing)
+        }
+        HookCommand::Uninstall(hooks) => selected_hooks(&hooks)
+            .into_iter()
+            .filter_map(|kind| uninstall_hook(&dir, kind).transpose())
+            .collect(),
+    }
+    .unwrap_or_else(|e| fail(e));
+
+    let mut stdout = stdout();
+    if changes.is_empty() {
+        execute!(stdout, Print("No commitz hooks found\n"))?;
+    }
+    for change in changes {
         execute!(
             stdout,
             SetForegroundColor(Color::DarkGreen),
-            Print("\n✅ Commit successful!\n"),
-            ResetColor,
+            Print(format!("✅ {}\n", change)),
+            ResetColor
         )?;
     }
 
-    terminal::disable_raw_mode()?;
     Ok(())
 }
+
+fn selected_hooks(selection: &HookSelection) -> Vec<HookKind> {
+    match (selection.commit_msg, selection.prepare_commit_msg) {
+        (true, false) => vec![HookKind::CommitMsg],
+        (false, true) => vec![HookKind::PrepareCommitMsg],
+        _ => vec![HookKind::CommitMsg, HookKind::PrepareCommitMsg],
+    }
+}
+
+fn run_changelog(_args: ChangelogArgs, _overrides: &[String]) -> io::Result<()> {
+    fail(io::Error::other(
+        "`commitz changelog` is not implemented yet",
+    ))
+}
+
+fn load_config(overrides: &[String]) -> io::Result<Config> {
+    match Config::load(overrides) {
+        Ok(config) => Ok(config),
+        Err(e) => config_error(e),
+    }
+}
+
+fn config_error(e: io::Error) -> ! {
+    let _ = execute!(
+        stdout(),
+        SetForegroundColor(Color::Red),
+        Print(format!("❌ Invalid commitz config: {}\n", e)),
+        ResetColor
+    );
+    std::process::exit(EXIT_FAILURE);
+}
+
+fn fail(e: io::Error) -> ! {
+    let _ = execute!(
+        io::stderr(),
+        SetForegroundColor(Color::Red),
+        Print(format!("❌ {}\n", e)),
+        ResetColor
+    );
+    std::process::exit(EXIT_FAILURE);
+}